@@ -9,6 +9,12 @@ export enum Anchor {
   Bottom = 5
 }
 
+export enum FullscreenMode {
+  None = 0,
+  Borderless = 1,
+  Exclusive = 2
+}
+
 export declare class LayoutBuilder {
   constructor();
   left(id: string, width: number, options?: Partial<PaneOptions>): this;
@@ -31,6 +37,74 @@ export interface PaneOptions {
   visible: boolean;
   anchor?: Anchor;
   dockOrder?: number;
+  /** Navigation allowlist for this pane, overriding the window's. */
+  allowedOrigins?: string[];
+  /** Crash recovery for this pane, overriding the window's. */
+  crashPolicy?: CrashPolicy;
+}
+
+/**
+ * What a pane does after its content crashes. 'reload' falls back to the
+ * error page if the pane crashes again within 10 seconds.
+ */
+export type CrashPolicy = 'keep' | 'reload' | 'show-error-page';
+
+/**
+ * A page asked for a permission. Answer any time; the page waits until you do.
+ * Without listeners, notifications are allowed and everything else denied.
+ */
+export interface PermissionRequest {
+  /**
+   * scheme://host:port of the pane's top-level page. The request may come
+   * from a frame inside it when `canRemember` is false.
+   */
+  origin: string;
+  /** e.g. 'notifications', 'geolocation', 'camera', 'microphone', 'bluetooth', 'device-info'. */
+  permission: string;
+  paneId: string;
+  /**
+   * True when the page has no frames, so the request is known to come from
+   * `origin`. Otherwise `remember` is ignored and saved answers don't apply.
+   */
+  canRemember: boolean;
+  /** With `remember`, the answer is kept for this origin in permissions.json and not asked again. */
+  allow(remember?: boolean): void;
+  deny(remember?: boolean): void;
+}
+
+/**
+ * A server or proxy asked for HTTP (Basic/Digest) credentials. Answer any
+ * time; without listeners the login is cancelled.
+ */
+export interface LoginRequest {
+  url: string;
+  /** scheme://host:port of the challenging server. */
+  origin: string;
+  isProxy: boolean;
+  paneId: string;
+  /** With `remember`, the credentials are reused for this origin until the app exits, never saved to disk. */
+  login(username: string, password: string, options?: { remember?: boolean }): void;
+  cancel(): void;
+}
+
+export interface LoadFailure {
+  url: string;
+  /**
+   * 'ERR_NAME_NOT_RESOLVED', 'ERR_CONNECTION_REFUSED', 'ERR_CONNECTION_RESET', 'ERR_TIMED_OUT',
+   * 'ERR_CERT_INVALID', 'ERR_FILE_NOT_FOUND', 'ERR_ACCESS_DENIED' or 'ERR_FAILED'.
+   */
+  code: string;
+  /** Servo's error text, or the file error for lotus-resource URLs. */
+  description: string;
+  /** False for a failed image, script or other subresource. */
+  isMainFrame: boolean;
+}
+
+export interface CrashInfo {
+  reason: string;
+  backtrace: string | null;
+  /** The policy applied. */
+  action: CrashPolicy;
 }
 
 export interface WindowOptions {
@@ -53,10 +127,107 @@ export interface WindowOptions {
   panes?: Array<Partial<PaneOptions>>;
   id?: string;
   wmClass?: string;
+  /** Remember each pane's zoom per origin (in zoom-levels.json next to window-state.json). */
+  persistZoom?: boolean;
+  /**
+   * Where panes may navigate: scheme prefixes ('lotus-resource://', 'https://example.com')
+   * or hosts ('example.com', '*.example.com'). Other navigations are blocked unless a
+   * 'will-navigate' listener allows them. Unset allows everything.
+   */
+  allowedOrigins?: string[];
+  /**
+   * Page under `root` (or a lotus-resource:// URL) shown when a document fails to load.
+   * `{{code}}`, `{{url}}` and `{{description}}` in it are replaced, HTML-escaped.
+   */
+  errorPage?: string;
+  /** What panes do after a crash. Default 'keep'. */
+  crashPolicy?: CrashPolicy;
+  /** Print each page's console output to Node's console, prefixed with window and pane id. */
+  mirrorConsole?: boolean;
+  /** How long (ms) a key waits for 'before-input' listeners before reaching the page. Default 50. */
+  beforeInputBudget?: number;
+  /** Display id (from app.getDisplays()) to open the window on. */
+  display?: string;
+  /** Center the window in the work area of `display`, or of the primary display. */
+  center?: boolean;
+  /** Owner window (or its id). The child stays above it, minimizes with it and opens centered on it. */
+  parent?: ServoWindow | string;
+  /** Block input to the owner's panes while this window is open. Requires `parent`. */
+  modal?: boolean;
+  /** PNG data, or a PNG path (absolute, relative to `root`, or inside the packaged VFS). */
+  icon?: Buffer | string;
   /** Alias for !frameless */
   frame?: boolean;
 }
 
+export interface DisplayBounds {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+export interface WindowBounds {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+export interface PaneInfo {
+  id: string;
+  url?: string;
+  title?: string;
+  /** Logical (CSS) pixels relative to the window. */
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+  zIndex: number;
+  visible: boolean;
+  anchor: Anchor;
+  dockOrder: number;
+  focused: boolean;
+  /** Page zoom factor, 1.0 = 100%. */
+  zoom: number;
+  canGoBack: boolean;
+  canGoForward: boolean;
+}
+
+export interface HistoryState {
+  /** URLs of the pane's session history, oldest first. */
+  entries: string[];
+  /** Index of the current entry in `entries`. */
+  index: number;
+  canGoBack: boolean;
+  canGoForward: boolean;
+}
+
+export interface WindowSnapshot {
+  /** Outer position and inner size in physical pixels. */
+  bounds: WindowBounds;
+  maximized: boolean;
+  minimized: boolean;
+  visible: boolean;
+  focused: boolean;
+  fullscreen: boolean;
+  scaleFactor: number;
+  title: string;
+  panes: PaneInfo[];
+}
+
+export interface DisplayInfo {
+  id: string;
+  name?: string;
+  /** Full monitor rect in physical desktop pixels. */
+  bounds: DisplayBounds;
+  /** Monitor rect minus panels and taskbars. */
+  workArea: DisplayBounds;
+  scaleFactor: number;
+  refreshRate?: number;
+  isPrimary: boolean;
+}
+
 export interface ResizePayload {
   width: number;
   height: number;
@@ -70,9 +241,21 @@ export declare class Pane extends EventEmitter {
   
   loadUrl(url: string): void;
   executeScript(script: string): void;
+  goBack(): void;
+  goForward(): void;
+  reload(): void;
+  stop(): void;
+  canGoBack(): Promise<boolean>;
+  canGoForward(): Promise<boolean>;
+  /** Navigation allowlist for this pane only; null falls back to the window's. */
+  setAllowedOrigins(origins: string[] | null): void;
+  setCrashPolicy(policy: CrashPolicy): void;
   setRect(x: number, y: number, width: number, height: number): void;
   setVisible(visible: boolean): void;
   focus(): void;
+  /** Page zoom factor, 1.0 = 100%. Clamped to 0.25 - 5.0; throws if it isn't a finite number. */
+  setZoom(factor: number): void;
+  getZoom(): Promise<number>;
   updateDragRegions(rects: any[]): void;
   remove(): void;
 
@@ -80,9 +263,30 @@ export declare class Pane extends EventEmitter {
    * Events:
    * - 'load-status': (status: 'started' | 'head-parsed' | 'complete')
    * - 'title-changed': (title: string)
+   * - 'url-changed': (url: string)
+   * - 'history-changed': (history: HistoryState)
+   * - 'favicon-changed': (favicon: string), a data:image/png URL
+   * - 'focus' / 'blur': the pane gained or lost keyboard focus
+   * - 'zoom-changed': (zoom: number)
+   * - 'console-message': (info: ConsoleMessage)
+   * - 'crashed': (info: CrashInfo)
+   * - 'load-failed': (info: LoadFailure)
+   * - 'permission-request': (request: PermissionRequest)
+   * - 'login': (request: LoginRequest)
+   * - 'will-navigate': (event: WillNavigateEvent)
    */
   on(event: 'load-status', listener: (status: 'started' | 'head-parsed' | 'complete') => void): this;
   on(event: 'title-changed', listener: (title: string) => void): this;
+  on(event: 'url-changed' | 'favicon-changed', listener: (value: string) => void): this;
+  on(event: 'history-changed', listener: (history: HistoryState) => void): this;
+  on(event: 'focus' | 'blur', listener: () => void): this;
+  on(event: 'zoom-changed', listener: (zoom: number) => void): this;
+  on(event: 'will-navigate', listener: (event: WillNavigateEvent) => void): this;
+  on(event: 'console-message', listener: (info: ConsoleMessage) => void): this;
+  on(event: 'crashed', listener: (info: CrashInfo) => void): this;
+  on(event: 'load-failed', listener: (info: LoadFailure) => void): this;
+  on(event: 'permission-request', listener: (request: PermissionRequest) => void): this;
+  on(event: 'login', listener: (request: LoginRequest) => void): this;
   on(event: string | symbol, listener: (...args: any[]) => void): this;
 }
 
@@ -111,6 +315,44 @@ export declare class ServoWindow extends EventEmitter {
   minimize(): void;
   unminimize(): void;
   focus(): void;
+  setFullscreen(mode: boolean | FullscreenMode | 'none' | 'borderless' | 'exclusive'): void;
+  /**
+   * Let clicks pass through the window. `forward` keeps delivering mouse movement
+   * to panes where the cursor position can be read (X11, Windows).
+   */
+  setIgnoreMouseEvents(ignore: boolean, options?: { forward?: boolean }): void;
+  /** 0.0 (invisible) to 1.0 (opaque). Needs `transparent: true` unless the window manager can fade the window (X11). */
+  setOpacity(opacity: number): void;
+  /** PNG data or path. Throws if the image can't be read or decoded. */
+  setIcon(icon?: Buffer | string): void;
+  /**
+   * Attach a menu bar; null removes it. On Linux it's only shown by a global
+   * menu (X11 with com.canonical.AppMenu.Registrar, e.g. KDE Plasma); elsewhere
+   * this throws and the current menu stays. Check Menu.isMenuBarSupported() first.
+   */
+  setMenu(menu: Menu | MenuItemTemplate[] | null): void;
+  getMenu(): Menu | null;
+  /** Move keyboard focus to the next pane in tab order, like Ctrl+F6 (Ctrl+Shift+F6 backwards). */
+  focusNextPane(backwards?: boolean): void;
+  /** Pane tab order. Panes not listed follow, top to bottom, left to right. */
+  setFocusOrder(paneIds: string[]): void;
+  /** Replace the window's navigation allowlist (see WindowOptions.allowedOrigins); null lifts it. */
+  setAllowedOrigins(origins: string[] | null): void;
+  /** Crash recovery for every pane without its own policy. */
+  setCrashPolicy(policy: CrashPolicy): void;
+  /** Handle a key combination while the window is focused; the page never sees it. */
+  registerAccelerator(accelerator: string, callback?: (win: ServoWindow, paneId: string) => void): void;
+  unregisterAccelerator(accelerator: string): void;
+
+  getState(): Promise<WindowSnapshot>;
+  getBounds(): Promise<WindowBounds>;
+  isMaximized(): Promise<boolean>;
+  isMinimized(): Promise<boolean>;
+  isVisible(): Promise<boolean>;
+  isFocused(): Promise<boolean>;
+  getScaleFactor(): Promise<number>;
+  getTitle(): Promise<string>;
+  getPanes(): Promise<PaneInfo[]>;
 
   sendToRenderer(channel: string, data: any, immediate?: boolean): void;
   sendToPaneRenderer(paneId: string, channel: string, data: any, immediate?: boolean): void;
@@ -127,6 +369,24 @@ export declare class ServoWindow extends EventEmitter {
    * - 'load-status': (status: string, paneId: string)
    * - 'title-changed': (title: string, paneId: string)
    * - 'file-drop': (data: { path: string })
+   * - 'fullscreen-changed': (fullscreen: boolean, mode: 'none' | 'borderless' | 'exclusive')
+   * - 'menu-activate': (info: MenuActivateInfo)
+   * - 'accelerator': (accelerator: string, paneId: string)
+   * - 'pane-focused' / 'pane-blurred': (paneId: string)
+   * - 'zoom-changed': (zoom: number, paneId: string)
+   * - 'url-changed' / 'favicon-changed': (value: string, paneId: string)
+   * - 'history-changed': (history: HistoryState, paneId: string)
+   * - 'rotate-gesture': (degrees: number, paneId: string), trackpad rotation; counter-clockwise is positive
+   * - 'before-input': (event: BeforeInputEvent, input: KeyInput), synchronous
+   * - 'will-navigate': (event: WillNavigateEvent), synchronous; only for URLs outside the allowlist
+   * - 'new-window-requested': (event: NewWindowRequestedEvent), synchronous
+   * - 'console-message': (info: ConsoleMessage)
+   * - 'pane-crashed': (info: CrashInfo, paneId: string)
+   * - 'load-failed': (info: LoadFailure, paneId: string)
+   * - 'permission-request': (request: PermissionRequest)
+   * - 'login': (request: LoginRequest)
+   * - 'notification-click' / 'notification-action' / 'notification-close' / 'notification-error':
+   *   (info: PageNotificationEvent) for notifications shown by a page
    */
   on(event: 'ready-to-show', listener: () => void): this;
   on(event: 'ready' | 'dom-ready', listener: (data: any) => void): this;
@@ -136,9 +396,98 @@ export declare class ServoWindow extends EventEmitter {
   on(event: 'load-status', listener: (status: string, paneId: string) => void): this;
   on(event: 'title-changed', listener: (title: string, paneId: string) => void): this;
   on(event: 'file-drop' | 'file-hover', listener: (data: { path: string }) => void): this;
+  on(event: 'fullscreen-changed', listener: (fullscreen: boolean, mode: 'none' | 'borderless' | 'exclusive') => void): this;
+  on(event: 'menu-activate', listener: (info: MenuActivateInfo) => void): this;
+  on(event: 'accelerator', listener: (accelerator: string, paneId: string) => void): this;
+  on(event: 'pane-focused' | 'pane-blurred', listener: (paneId: string) => void): this;
+  on(event: 'zoom-changed', listener: (zoom: number, paneId: string) => void): this;
+  on(event: 'url-changed' | 'favicon-changed', listener: (value: string, paneId: string) => void): this;
+  on(event: 'history-changed', listener: (history: HistoryState, paneId: string) => void): this;
+  on(event: 'rotate-gesture', listener: (degrees: number, paneId: string) => void): this;
+  on(event: 'before-input', listener: (event: BeforeInputEvent, input: KeyInput) => void): this;
+  on(event: 'will-navigate', listener: (event: WillNavigateEvent) => void): this;
+  on(event: 'new-window-requested', listener: (event: NewWindowRequestedEvent) => void): this;
+  on(event: 'console-message', listener: (info: ConsoleMessage) => void): this;
+  on(event: 'pane-crashed', listener: (info: CrashInfo, paneId: string) => void): this;
+  on(event: 'load-failed', listener: (info: LoadFailure, paneId: string) => void): this;
+  on(event: 'permission-request', listener: (request: PermissionRequest) => void): this;
+  on(event: 'login', listener: (request: LoginRequest) => void): this;
+  on(event: 'notification-click' | 'notification-action' | 'notification-close' | 'notification-error', listener: (info: PageNotificationEvent) => void): this;
   on(event: string | symbol, listener: (...args: any[]) => void): this;
 }
 
+export interface KeyInput {
+  type: 'keyDown' | 'keyUp';
+  /** DOM `KeyboardEvent.key`, e.g. 'r', 'Enter', 'F11'. */
+  key: string;
+  /** DOM `KeyboardEvent.code`, e.g. 'KeyR'. */
+  code: string;
+  isAutoRepeat: boolean;
+  /** DOM `KeyboardEvent.location`: 0 standard, 1 left, 2 right, 3 numpad. */
+  location: number;
+  shift: boolean;
+  control: boolean;
+  alt: boolean;
+  meta: boolean;
+}
+
+export interface BeforeInputEvent {
+  paneId: string;
+  /** Keep the key from reaching the page. Only honoured during the listener call. */
+  preventDefault(): void;
+  readonly defaultPrevented: boolean;
+}
+
+export interface WillNavigateEvent {
+  url: string;
+  paneId: string;
+  /** Let the navigation through despite the allowlist. */
+  allow(): void;
+  /** Keep it blocked and open the URL in the system browser instead (http, https and mailto only). */
+  openExternal(): void;
+  /** 'deny' unless allow() or openExternal() was called. Only honoured during the listener call. */
+  readonly action: 'allow' | 'deny' | 'external';
+}
+
+/** A page's console output. Servo doesn't report the source URL or line number. */
+export interface ConsoleMessage {
+  level: 'log' | 'debug' | 'info' | 'warn' | 'error' | 'trace';
+  message: string;
+  windowId: string;
+  paneId: string;
+}
+
+/**
+ * A page called window.open() or followed a target=_blank link. Emitted as
+ * soon as the popup exists; it loads nothing, and is closed, unless the
+ * listener opens it.
+ */
+export interface NewWindowRequestedEvent {
+  /** Absolute URL the popup is navigating to, or '' for a bare window.open() (the popup stays about:blank). */
+  url: string;
+  /**
+   * The window name passed to window.open(), or the link's target. Only a
+   * hint: the engine doesn't report it, so it's recorded by the page, which
+   * can alter it. Don't base security decisions on it.
+   */
+  target: string;
+  /** window.open()'s features string, e.g. 'width=400,height=300'; '' for links. A page-recorded hint, like `target`. */
+  features: string;
+  /** The opener pane. */
+  paneId: string;
+  /**
+   * Show the popup as a pane of this window. It keeps its `opener`
+   * (window.opener, postMessage) and gets an id like 'popup-1'.
+   */
+  openAsPane(options?: { x?: number, y?: number, width?: number, height?: number, zIndex?: number }): Pane;
+  /**
+   * Load the URL in a new window instead. Windows can't share a popup's
+   * rendering context, so the popup is closed: this page has no `opener`, and
+   * the handle window.open() returned to the opener is dead.
+   */
+  openAsWindow(options?: WindowOptions): ServoWindow;
+}
+
 export interface IpcMain extends EventEmitter {
   send(channel: string, data: any): void;
   sendTo(windowId: string, channel: string, data: any): void;
@@ -147,8 +496,190 @@ export interface IpcMain extends EventEmitter {
 
 export const ipcMain: IpcMain;
 
-export const app: {
+export interface App extends EventEmitter {
   quit(): void;
   warmup(): void;
   initVfs(): void;
-};
+  getDisplays(): DisplayInfo[];
+  /** Remembered permission answers: origin -> permission -> granted. */
+  getPermissionDecisions(): Record<string, Record<string, boolean>>;
+  /** Remember an answer for an origin; null forgets it. */
+  setPermissionDecision(origin: string, permission: string, granted: boolean | null): void;
+  /** Forget remembered answers for one origin, or all of them. */
+  clearPermissionDecisions(origin?: string): void;
+  /** Forget logins remembered with `login(..., { remember: true })`, for one origin or all. */
+  clearAuthCache(origin?: string): void;
+  /** Open an http(s) or mailto URL with the system's default handler. Throws for other schemes. */
+  openExternal(url: string): void;
+  /**
+   * Grab an accelerator (e.g. 'CmdOrCtrl+Shift+Space') system-wide. The callback runs on
+   * each press, even when no Lotus window is focused. Rejects if the accelerator is invalid,
+   * already taken by another application, or declined by the compositor.
+   */
+  registerGlobalShortcut(accelerator: string, callback?: () => void): Promise<void>;
+  unregisterGlobalShortcut(accelerator: string): Promise<void>;
+
+  /**
+   * Events:
+   * - 'display-added' / 'display-removed' / 'display-changed': (display: DisplayInfo)
+   * - 'global-shortcut': (accelerator: string, state: 'pressed' | 'released')
+   */
+  on(event: 'display-added' | 'display-removed' | 'display-changed', listener: (display: DisplayInfo) => void): this;
+  on(event: 'global-shortcut', listener: (accelerator: string, state: 'pressed' | 'released') => void): this;
+  on(event: string | symbol, listener: (...args: any[]) => void): this;
+}
+
+export const app: App;
+
+export interface MenuItemTemplate {
+  id?: string;
+  label?: string;
+  type?: 'normal' | 'separator' | 'checkbox' | 'radio' | 'submenu';
+  checked?: boolean;
+  enabled?: boolean;
+  visible?: boolean;
+  accelerator?: string;
+  submenu?: MenuItemTemplate[] | Menu;
+  /** Tray menus pass only the item; window menus also pass the window and pane. */
+  click?: (item: MenuItemTemplate, win?: ServoWindow, pane?: Pane) => void;
+}
+
+export interface MenuActivateInfo {
+  menuId: string;
+  itemId: string;
+  paneId: string;
+  checked?: boolean | null;
+}
+
+export class MenuItem implements MenuItemTemplate {
+  constructor(options?: MenuItemTemplate);
+  id?: string;
+  label?: string;
+  type?: 'normal' | 'separator' | 'checkbox' | 'radio' | 'submenu';
+  checked?: boolean;
+  enabled?: boolean;
+  visible?: boolean;
+  accelerator?: string;
+  submenu?: Menu;
+  click?: (item: MenuItemTemplate, win?: ServoWindow, pane?: Pane) => void;
+}
+
+export class Menu extends EventEmitter {
+  /** Whether ServoWindow.setMenu() can show a menu bar here (always on Windows and macOS). */
+  static isMenuBarSupported(): boolean;
+  static buildFromTemplate(template: Array<MenuItemTemplate | MenuItem>): Menu;
+  readonly id: string;
+  readonly items: MenuItem[];
+  append(item: MenuItemTemplate | MenuItem): void;
+  insert(position: number, item: MenuItemTemplate | MenuItem): void;
+  getMenuItemById(id: string): MenuItem | null;
+  /** Show as a context menu. x/y are logical pixels relative to the pane; omitted = at the pointer. */
+  popup(options: { window: ServoWindow; pane?: Pane | string; x?: number; y?: number }): void;
+
+  on(event: 'activate', listener: (item: MenuItem | string, win?: ServoWindow, pane?: Pane) => void): this;
+  on(event: string | symbol, listener: (...args: any[]) => void): this;
+}
+
+export interface TrayOptions {
+  /** PNG data or path. */
+  icon?: Buffer | string;
+  tooltip?: string;
+  menu?: MenuItemTemplate[];
+}
+
+export class Tray extends EventEmitter {
+  constructor(options?: TrayOptions);
+  readonly id: string;
+  setIcon(icon: Buffer | string): void;
+  setTooltip(tooltip: string): void;
+  setMenu(template: MenuItemTemplate[]): void;
+  destroy(): void;
+
+  on(event: 'click', listener: (info: { button: 'left' | 'middle' | 'right'; x: number; y: number }) => void): this;
+  on(event: 'menu-activate', listener: (itemId: string, checked?: boolean) => void): this;
+  on(event: 'error', listener: (err: Error) => void): this;
+  on(event: string | symbol, listener: (...args: any[]) => void): this;
+}
+
+export interface FileFilter {
+  name: string;
+  /** Extensions without the dot; '*' matches everything. */
+  extensions: string[];
+}
+
+export interface OpenDialogOptions {
+  title?: string;
+  defaultPath?: string;
+  buttonLabel?: string;
+  filters?: FileFilter[];
+  multiSelections?: boolean;
+  /** Pick folders instead of files. */
+  directory?: boolean;
+}
+
+export interface SaveDialogOptions {
+  title?: string;
+  defaultPath?: string;
+  buttonLabel?: string;
+  filters?: FileFilter[];
+}
+
+export interface MessageBoxOptions {
+  type?: 'none' | 'info' | 'warning' | 'error' | 'question';
+  title?: string;
+  message: string;
+  detail?: string;
+  /** Defaults to ['OK']. */
+  buttons?: string[];
+  defaultId?: number;
+  /** Returned when the box is dismissed without a button. */
+  cancelId?: number;
+}
+
+export interface Dialog {
+  showOpenDialog(win: ServoWindow, options?: OpenDialogOptions): Promise<{ canceled: boolean; filePaths: string[] }>;
+  showOpenDialog(options?: OpenDialogOptions): Promise<{ canceled: boolean; filePaths: string[] }>;
+  showSaveDialog(win: ServoWindow, options?: SaveDialogOptions): Promise<{ canceled: boolean; filePath?: string }>;
+  showSaveDialog(options?: SaveDialogOptions): Promise<{ canceled: boolean; filePath?: string }>;
+  showMessageBox(win: ServoWindow, options: MessageBoxOptions | string): Promise<{ response: number }>;
+  showMessageBox(options: MessageBoxOptions | string): Promise<{ response: number }>;
+}
+
+export const dialog: Dialog;
+
+export interface NotificationOptions {
+  title: string;
+  body?: string;
+  /** PNG path, on disk or in the VFS. */
+  icon?: string;
+  actions?: Array<{ id: string; label: string }>;
+  /** Stay on screen until dismissed. */
+  requireInteraction?: boolean;
+  silent?: boolean;
+}
+
+export type NotificationCloseReason = 'expired' | 'dismissed' | 'closed' | 'unknown';
+
+export interface PageNotificationEvent {
+  notificationId: string;
+  paneId: string;
+  actionId?: string;
+  reason?: NotificationCloseReason;
+  message?: string;
+}
+
+export class Notification extends EventEmitter {
+  constructor(options: NotificationOptions);
+  /** Set once shown. */
+  readonly id: string | null;
+  show(): this;
+  close(): void;
+
+  on(event: 'click', listener: () => void): this;
+  on(event: 'action', listener: (actionId: string) => void): this;
+  on(event: 'close', listener: (reason: NotificationCloseReason) => void): this;
+  on(event: 'error', listener: (err: Error) => void): this;
+  on(event: string | symbol, listener: (...args: any[]) => void): this;
+}
+
+export function showNotification(options: NotificationOptions): Notification;
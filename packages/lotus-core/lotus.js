@@ -41,7 +41,17 @@ if (process.platform === 'win32') {
 }
 
 
-const { App, createWindow } = require('./index.js');
+const {
+    App,
+    createWindow,
+    Tray: NativeTray,
+    showOpenDialog: nativeShowOpenDialog,
+    showSaveDialog: nativeShowSaveDialog,
+    showMessageBox: nativeShowMessageBox,
+    showNotification: nativeShowNotification,
+    closeNotification: nativeCloseNotification,
+    menuBarSupported
+} = require('./index.js');
 const EventEmitter = require('events');
 let msgpackr;
 try {
@@ -188,8 +198,17 @@ if (isProfiling) {
 }
 
 const ipcMain = new IpcMain();
+const appEvents = new EventEmitter();
 let globalApp = null;
 const windows = new Map();
+const trays = new Map();
+// Menus shown as a menu bar or context menu, by id, so activations find their click handlers
+const menus = new Map();
+let nextMenuId = 1;
+const notifications = new Map();
+const NOTIFICATION_EVENTS = ['notification-click', 'notification-action', 'notification-close', 'notification-error'];
+// Accelerator -> callback for app.registerGlobalShortcut
+const globalShortcuts = new Map();
 const eventQueue = new Map(); // windowId -> Array of pending events
 const globalPackers = new Map();
 const globalUnpackers = new Map();
@@ -299,6 +318,35 @@ function ensureApp() {
                 return;
             }
 
+            // Monitor hotplug / reconfiguration is app-wide, not tied to a window
+            if (msg.event === 'display-added' || msg.event === 'display-removed' || msg.event === 'display-changed') {
+                appEvents.emit(msg.event, msg.display);
+                return;
+            }
+
+            // Fires with no Lotus window focused; the callback runs on press only
+            if (msg.event === 'global-shortcut') {
+                const callback = globalShortcuts.get(msg.accelerator);
+                if (callback && msg.state === 'pressed') callback();
+                appEvents.emit('global-shortcut', msg.accelerator, msg.state);
+                return;
+            }
+
+            if (msg.event === 'tray-click' || msg.event === 'tray-menu-activate' || msg.event === 'tray-error') {
+                const tray = trays.get(msg.tray_id);
+                if (tray) tray._handleEvent(msg);
+                return;
+            }
+
+            // Notifications shown from Node; page notifications carry a window id instead
+            if (NOTIFICATION_EVENTS.includes(msg.event) && notifications.has(msg.notification_id)) {
+                notifications.get(msg.notification_id)._handleEvent(msg);
+                return;
+            }
+
+            // Any other app-wide event has no window to go to (and nothing would ever drain it)
+            if (windowId === 'global') return;
+
             // All other events should have a window_id
             const win = windows.get(windowId);
             
@@ -417,6 +465,11 @@ function processEvent(win, msg) {
         return;
     }
 
+    if (msg.event === 'fullscreen-changed') {
+        if (win) win.emit('fullscreen-changed', msg.fullscreen, msg.mode);
+        return;
+    }
+
     if (msg.event === 'focused') {
         if (win) win.emit('focus');
         return;
@@ -427,6 +480,247 @@ function processEvent(win, msg) {
         return;
     }
 
+    if (msg.event === 'accelerator') {
+        if (win) {
+            const callback = win._accelerators.get(msg.accelerator);
+            if (callback) callback(win, paneId);
+            win.emit('accelerator', msg.accelerator, paneId);
+        }
+        return;
+    }
+
+    // Listeners must decide synchronously; the key is held until we answer
+    // (or the window's budget runs out).
+    if (msg.event === 'before-input') {
+        let prevented = false;
+        const event = {
+            paneId: paneId,
+            preventDefault() { prevented = true; },
+            get defaultPrevented() { return prevented; }
+        };
+        if (win) {
+            try {
+                win.emit('before-input', event, msg.input);
+            } finally {
+                win.handle.resolveInput(msg.seq, prevented);
+            }
+        }
+        return;
+    }
+
+    // A navigation outside the allowlist; blocked unless a listener says otherwise
+    if (msg.event === 'will-navigate') {
+        let action = 'deny';
+        const event = {
+            url: msg.url,
+            paneId: paneId,
+            allow() { action = 'allow'; },
+            openExternal() { action = 'external'; },
+            get action() { return action; }
+        };
+        if (win) {
+            try {
+                win.emit('will-navigate', event);
+                const pane = win.panes.get(paneId);
+                if (pane) pane.emit('will-navigate', event);
+            } finally {
+                win.handle.resolveNavigation(msg.seq, action);
+            }
+        }
+        return;
+    }
+
+    // window.open / target=_blank. The popup already exists, hidden; it's closed
+    // unless a listener adopts it as a pane or reopens its URL as a window.
+    if (msg.event === 'new-window-requested') {
+        if (!win) return;
+        let adopted = null;
+        const event = {
+            url: msg.url,
+            target: msg.target,
+            features: msg.features,
+            paneId: paneId,
+            openAsPane(options = {}) {
+                if (adopted) return adopted;
+                const { x = 0, y = 0, width = 0, height = 0, zIndex = 0 } = options;
+                win.handle.adoptPopup(msg.seq, x, y, width, height, zIndex);
+                adopted = new Pane(win, msg.popup_pane_id);
+                win.panes.set(adopted.id, adopted);
+                return adopted;
+            },
+            openAsWindow(options = {}) {
+                // The popup itself is closed; the opener's handle to it goes dead.
+                return new ServoWindow({ ...options, initialUrl: msg.url || 'about:blank' });
+            }
+        };
+        try {
+            win.emit('new-window-requested', event);
+        } finally {
+            if (!adopted) win.handle.discardPopup(msg.seq);
+        }
+        return;
+    }
+
+    if (msg.event === 'menu-activate') {
+        const menu = menus.get(msg.menu_id);
+        if (menu) menu._handleActivate(win, msg);
+        if (win) win.emit('menu-activate', { menuId: msg.menu_id, itemId: msg.item_id, paneId: paneId, checked: msg.checked });
+        return;
+    }
+
+    // Notifications shown by a page through the web Notification API
+    if (NOTIFICATION_EVENTS.includes(msg.event)) {
+        if (win) {
+            win.emit(msg.event, {
+                notificationId: msg.notification_id,
+                paneId: paneId,
+                actionId: msg.action_id,
+                reason: msg.reason,
+                message: msg.message
+            });
+        }
+        return;
+    }
+
+    if (msg.event === 'url-changed' || msg.event === 'favicon-changed') {
+        if (win) {
+            const value = msg.event === 'url-changed' ? msg.url : msg.favicon;
+            win.emit(msg.event, value, paneId);
+            const pane = win.panes.get(paneId);
+            if (pane) pane.emit(msg.event, value);
+        }
+        return;
+    }
+
+    if (msg.event === 'history-changed') {
+        if (win) {
+            const history = {
+                entries: msg.entries,
+                index: msg.index,
+                canGoBack: msg.can_go_back,
+                canGoForward: msg.can_go_forward
+            };
+            win.emit('history-changed', history, paneId);
+            const pane = win.panes.get(paneId);
+            if (pane) pane.emit('history-changed', history);
+        }
+        return;
+    }
+
+    // Answered whenever a listener calls allow()/deny(); without listeners,
+    // notifications are allowed and everything else is denied.
+    if (msg.event === 'permission-request') {
+        if (!win) return;
+        let answered = false;
+        const respond = (allow, remember) => {
+            if (answered) return;
+            answered = true;
+            win.handle.resolvePermission(msg.seq, allow, !!remember);
+        };
+        const request = {
+            origin: msg.origin,
+            permission: msg.permission,
+            paneId: paneId,
+            canRemember: !!msg.can_remember,
+            allow(remember = false) { respond(true, remember); },
+            deny(remember = false) { respond(false, remember); }
+        };
+        const pane = win.panes.get(paneId);
+        const listeners = win.listenerCount('permission-request') + (pane ? pane.listenerCount('permission-request') : 0);
+        if (listeners === 0) {
+            respond(msg.permission === 'notifications', false);
+        } else {
+            win.emit('permission-request', request);
+            if (pane) pane.emit('permission-request', request);
+        }
+        return;
+    }
+
+    // HTTP auth challenge; cancelled unless a listener logs in
+    if (msg.event === 'login') {
+        if (!win) return;
+        let answered = false;
+        const respond = (username, password, remember) => {
+            if (answered) return;
+            answered = true;
+            win.handle.resolveLogin(msg.seq, username, password, !!remember);
+        };
+        const request = {
+            url: msg.url,
+            origin: msg.origin,
+            isProxy: msg.is_proxy,
+            paneId: paneId,
+            login(username, password, options = {}) { respond(username, password, options.remember); },
+            cancel() { respond(null, null, false); }
+        };
+        const pane = win.panes.get(paneId);
+        const listeners = win.listenerCount('login') + (pane ? pane.listenerCount('login') : 0);
+        if (listeners === 0) {
+            request.cancel();
+        } else {
+            win.emit('login', request);
+            if (pane) pane.emit('login', request);
+        }
+        return;
+    }
+
+    if (msg.event === 'load-failed') {
+        if (win) {
+            const info = { url: msg.url, code: msg.code, description: msg.description, isMainFrame: msg.main_frame };
+            win.emit('load-failed', info, paneId);
+            const pane = win.panes.get(paneId);
+            if (pane) pane.emit('load-failed', info);
+        }
+        return;
+    }
+
+    if (msg.event === 'pane-crashed') {
+        if (win) {
+            const info = { reason: msg.reason, backtrace: msg.backtrace || null, action: msg.action };
+            win.emit('pane-crashed', info, paneId);
+            const pane = win.panes.get(paneId);
+            if (pane) pane.emit('crashed', info);
+        }
+        return;
+    }
+
+    if (msg.event === 'console-message') {
+        if (win) {
+            const info = { level: msg.level, message: msg.message, windowId: windowId, paneId: paneId };
+            if (win._mirrorConsole) {
+                const method = ['log', 'debug', 'info', 'warn', 'error'].includes(msg.level) ? msg.level : 'log';
+                console[method](`[${windowId}:${paneId}]`, msg.message);
+            }
+            win.emit('console-message', info);
+            const pane = win.panes.get(paneId);
+            if (pane) pane.emit('console-message', info);
+        }
+        return;
+    }
+
+    if (msg.event === 'zoom-changed') {
+        if (win) {
+            win.emit('zoom-changed', msg.zoom, paneId);
+            const pane = win.panes.get(paneId);
+            if (pane) pane.emit('zoom-changed', msg.zoom);
+        }
+        return;
+    }
+
+    if (msg.event === 'pane-focused' || msg.event === 'pane-blurred') {
+        if (win) {
+            win.emit(msg.event, paneId);
+            const pane = win.panes.get(paneId);
+            if (pane) pane.emit(msg.event === 'pane-focused' ? 'focus' : 'blur');
+        }
+        return;
+    }
+
+    if (msg.event === 'rotate-gesture') {
+        if (win) win.emit('rotate-gesture', msg.degrees, paneId);
+        return;
+    }
+
     if (msg.event === 'file-hover') {
         if (win) win.emit('file-hover', { path: msg.path });
         return;
@@ -503,6 +797,12 @@ const Anchor = {
     Bottom: 5
 };
 
+const FullscreenMode = {
+    None: 0,
+    Borderless: 1,
+    Exclusive: 2
+};
+
 class LayoutBuilder {
     constructor() {
         this._panes = [];
@@ -602,6 +902,32 @@ class Pane extends EventEmitter {
         this.window.handle.executeScriptInPane(this.id, script);
     }
 
+    goBack() {
+        this.window.handle.goBackInPane(this.id);
+    }
+
+    goForward() {
+        this.window.handle.goForwardInPane(this.id);
+    }
+
+    reload() {
+        this.window.handle.reloadPane(this.id);
+    }
+
+    stop() {
+        this.window.handle.stopPane(this.id);
+    }
+
+    /** @returns {Promise<boolean>} */
+    canGoBack() {
+        return this.window.handle.canGoBackInPane(this.id);
+    }
+
+    /** @returns {Promise<boolean>} */
+    canGoForward() {
+        return this.window.handle.canGoForwardInPane(this.id);
+    }
+
     setRect(x, y, width, height) {
         this.window.handle.setPaneRect(this.id, x, y, width, height);
     }
@@ -618,6 +944,29 @@ class Pane extends EventEmitter {
         this.window.handle.focusPane(this.id);
     }
 
+    /**
+     * Navigation allowlist for this pane only, overriding the window's.
+     * @param {string[] | null} origins null falls back to the window's list
+     */
+    setAllowedOrigins(origins) {
+        this.window.handle.setAllowedOrigins(this.id, origins || null);
+    }
+
+    /** @param {'keep' | 'reload' | 'show-error-page'} policy */
+    setCrashPolicy(policy) {
+        this.window.handle.setCrashPolicy(this.id, policy);
+    }
+
+    /** Page zoom factor, 1.0 = 100%. Clamped to 0.25 - 5.0. */
+    setZoom(factor) {
+        this.window.handle.setPaneZoom(this.id, factor);
+    }
+
+    /** @returns {Promise<number>} */
+    getZoom() {
+        return this.window.handle.getPaneZoom(this.id);
+    }
+
     updateDragRegions(rects) {
         this.window.handle.updatePaneDragRegions(this.id, JSON.stringify(rects));
     }
@@ -659,6 +1008,15 @@ class ServoWindow extends EventEmitter {
         };
 
         const finalOptions = { ...defaultOptions, ...options };
+        if (finalOptions.parent instanceof ServoWindow) {
+            finalOptions.parent = finalOptions.parent.id;
+        }
+        // Rust options only carry a path; raw PNG data is applied after creation.
+        let iconBuffer = null;
+        if (Buffer.isBuffer(finalOptions.icon)) {
+            iconBuffer = finalOptions.icon;
+            delete finalOptions.icon;
+        }
         
         // If the user provided panes, ensure they are in the format Rust expects
         if (finalOptions.panes && Array.isArray(finalOptions.panes)) {
@@ -672,7 +1030,9 @@ class ServoWindow extends EventEmitter {
                 zIndex: p.zIndex || 0,
                 visible: p.visible !== false,
                 anchor: p.anchor,
-                dockOrder: p.dockOrder
+                dockOrder: p.dockOrder,
+                allowedOrigins: p.allowedOrigins,
+                crashPolicy: p.crashPolicy
             }));
         }
 
@@ -693,6 +1053,7 @@ class ServoWindow extends EventEmitter {
 
         this.handle = createWindow(finalOptions);
         this.id = this.handle.getId();
+        if (iconBuffer) this.handle.setIcon(iconBuffer);
         
         // Initialize the event queue for this window immediately.
         // This ensures that all events arriving during the constructor are queued
@@ -701,6 +1062,19 @@ class ServoWindow extends EventEmitter {
         if (!eventQueue.has(this.id)) eventQueue.set(this.id, []);
 
         this.panes = new Map();
+        this._accelerators = new Map();
+        this._mirrorConsole = !!finalOptions.mirrorConsole;
+        // Keys are only held back for Node while someone is listening
+        this.on('newListener', (event) => {
+            if (event === 'before-input' && this.listenerCount('before-input') === 0) {
+                this.handle.setBeforeInput(true, finalOptions.beforeInputBudget);
+            }
+        });
+        this.on('removeListener', (event) => {
+            if (event === 'before-input' && this.listenerCount('before-input') === 0) {
+                this.handle.setBeforeInput(false);
+            }
+        });
         this._batchQueue = [];
         this._batchBytes = 0;
         this._paneBatchQueues = new Map();
@@ -751,6 +1125,8 @@ class ServoWindow extends EventEmitter {
         }
         const { url = 'about:blank', x = 0, y = 0, width = 0, height = 0, zIndex = 0, anchor = 0, dockOrder = 0 } = options;
         this.handle.createPane(id, url, x, y, width, height, zIndex, anchor, dockOrder);
+        if (options.allowedOrigins) this.handle.setAllowedOrigins(id, options.allowedOrigins);
+        if (options.crashPolicy) this.handle.setCrashPolicy(id, options.crashPolicy);
         const pane = new Pane(this, id);
         this.panes.set(id, pane);
         return pane;
@@ -991,19 +1367,442 @@ class ServoWindow extends EventEmitter {
             this.handle.focus();
         }
     }
+
+    /**
+     * Enter or leave fullscreen. Accepts a boolean (true = borderless),
+     * a FullscreenMode value, or the strings 'borderless' / 'exclusive' / 'none'.
+     */
+    setFullscreen(mode) {
+        if (mode === true || mode === 'borderless') mode = FullscreenMode.Borderless;
+        else if (mode === 'exclusive') mode = FullscreenMode.Exclusive;
+        else if (!mode || mode === 'none') mode = FullscreenMode.None;
+        this.handle.setFullscreen(mode);
+    }
+
+    /**
+     * Let clicks pass through the window. With { forward: true } panes still
+     * receive mouse movement so overlays can react to hover.
+     */
+    setIgnoreMouseEvents(ignore, options = {}) {
+        this.handle.setIgnoreMouseEvents(!!ignore, !!options.forward);
+    }
+
+    setOpacity(opacity) {
+        this.handle.setOpacity(opacity);
+    }
+
+    /** Attach a Menu (or template) as the window's menu bar. Pass null to remove it. */
+    setMenu(menu) {
+        if (!menu) {
+            if (this._menu) menus.delete(this._menu.id);
+            this._menu = null;
+            this.handle.removeMenu();
+            return;
+        }
+        const built = menu instanceof Menu ? menu : Menu.buildFromTemplate(menu);
+        // Throws where no menu bar can be shown (see Menu.isMenuBarSupported()); the old menu stays.
+        this.handle.setMenu(built.id, built._build());
+        if (this._menu) menus.delete(this._menu.id);
+        this._menu = built;
+        menus.set(built.id, built);
+    }
+
+    getMenu() {
+        return this._menu || null;
+    }
+
+    /** Move keyboard focus to the next pane in tab order (Ctrl+F6), or the previous one. */
+    focusNextPane(backwards = false) {
+        this.handle.focusNextPane(backwards);
+    }
+
+    /**
+     * Set the pane tab order for Ctrl+F6 / focusNextPane(). Panes not listed
+     * follow, top to bottom and left to right.
+     * @param {string[]} paneIds
+     */
+    setFocusOrder(paneIds) {
+        this.handle.setFocusOrder(paneIds);
+    }
+
+    /**
+     * Restrict where panes may navigate, e.g. ['lotus-resource://', 'example.com'].
+     * Other navigations are blocked unless a 'will-navigate' listener allows them.
+     * A pane's own list (pane.setAllowedOrigins) wins. Pass null to lift the restriction.
+     * @param {string[] | null} origins
+     */
+    setAllowedOrigins(origins) {
+        this.handle.setAllowedOrigins(null, origins || null);
+    }
+
+    /**
+     * What panes do after a crash, unless a pane sets its own policy.
+     * @param {'keep' | 'reload' | 'show-error-page'} policy
+     */
+    setCrashPolicy(policy) {
+        this.handle.setCrashPolicy(null, policy);
+    }
+
+    /**
+     * Handle a key combination while this window is focused, before the page sees it.
+     * @param {string} accelerator e.g. 'CmdOrCtrl+R', 'F11'
+     * @param {Function} [callback] Called with (win, paneId)
+     */
+    registerAccelerator(accelerator, callback) {
+        this.handle.registerAccelerator(accelerator);
+        if (callback) this._accelerators.set(accelerator, callback);
+    }
+
+    unregisterAccelerator(accelerator) {
+        this._accelerators.delete(accelerator);
+        this.handle.unregisterAccelerator(accelerator);
+    }
+
+    /** Set the window icon from PNG data or a PNG path. Call with no argument to reset. */
+    setIcon(icon) {
+        this.handle.setIcon(icon);
+    }
+
+    // Awaitable getters: each resolves with the state read on the event loop thread.
+    getState() {
+        return this.handle.getState();
+    }
+
+    getBounds() {
+        return this.handle.getBounds();
+    }
+
+    isMaximized() {
+        return this.handle.isMaximized();
+    }
+
+    isMinimized() {
+        return this.handle.isMinimized();
+    }
+
+    isVisible() {
+        return this.handle.isVisible();
+    }
+
+    isFocused() {
+        return this.handle.isFocused();
+    }
+
+    getScaleFactor() {
+        return this.handle.getScaleFactor();
+    }
+
+    getTitle() {
+        return this.handle.getTitle();
+    }
+
+    getPanes() {
+        return this.handle.getPanes();
+    }
+}
+
+/**
+ * Convert a menu template ({ label, type, checked, enabled, visible, accelerator,
+ * submenu, click }) into the plain objects the native side expects. Items without
+ * an id get a generated one; `registry` maps ids back to template items so click
+ * handlers can be called when the native menu reports an activation.
+ */
+function buildMenuTemplate(template, registry) {
+    return template.map((item) => {
+        const id = item.id || `menu-item-${registry.size + 1}`;
+        registry.set(id, item);
+        return {
+            id,
+            label: item.label,
+            type: item.type || (item.submenu ? 'submenu' : 'normal'),
+            checked: item.checked,
+            enabled: item.enabled,
+            visible: item.visible,
+            accelerator: item.accelerator,
+            submenu: item.submenu
+                ? buildMenuTemplate(item.submenu instanceof Menu ? item.submenu.items : item.submenu, registry)
+                : undefined
+        };
+    });
+}
+
+class MenuItem {
+    /**
+     * @param {object} options Same fields as a menu template entry. `submenu`
+     * may be a Menu or a template array.
+     */
+    constructor(options = {}) {
+        Object.assign(this, options);
+        if (Array.isArray(options.submenu)) this.submenu = Menu.buildFromTemplate(options.submenu);
+    }
+}
+
+class Menu extends EventEmitter {
+    constructor() {
+        super();
+        this.id = `menu-${nextMenuId++}`;
+        this.items = [];
+        this._registry = new Map();
+    }
+
+    /** Whether setMenu() can show a menu bar on this desktop. */
+    static isMenuBarSupported() {
+        return menuBarSupported();
+    }
+
+    static buildFromTemplate(template) {
+        const menu = new Menu();
+        for (const item of template) menu.append(item);
+        return menu;
+    }
+
+    append(item) {
+        this.items.push(item instanceof MenuItem ? item : new MenuItem(item));
+    }
+
+    insert(position, item) {
+        this.items.splice(position, 0, item instanceof MenuItem ? item : new MenuItem(item));
+    }
+
+    getMenuItemById(id) {
+        const find = (items) => {
+            for (const item of items) {
+                if (item.id === id) return item;
+                if (item.submenu instanceof Menu) {
+                    const found = find(item.submenu.items);
+                    if (found) return found;
+                }
+            }
+            return null;
+        };
+        return find(this.items);
+    }
+
+    /**
+     * Show as a native context menu over a pane.
+     * @param {object} options
+     * @param {ServoWindow} options.window
+     * @param {Pane|string} [options.pane] Defaults to the main pane
+     * @param {number} [options.x] Logical pixels relative to the pane; defaults to the pointer
+     * @param {number} [options.y]
+     */
+    popup(options = {}) {
+        const win = options.window;
+        if (!win) throw new Error('Menu.popup requires a window');
+        const paneId = options.pane ? (typeof options.pane === 'string' ? options.pane : options.pane.id) : 'main';
+        menus.set(this.id, this);
+        win.handle.popupMenu(paneId, this.id, this._build(), options.x, options.y);
+    }
+
+    _build() {
+        this._registry = new Map();
+        return buildMenuTemplate(this.items, this._registry);
+    }
+
+    _handleActivate(win, msg) {
+        const item = this._registry.get(msg.item_id);
+        if (item && msg.checked !== null && msg.checked !== undefined) {
+            item.checked = msg.checked;
+            // Keep the rest of a radio group in sync with the native menu.
+            if (item.type === 'radio' && msg.checked) {
+                const siblings = findSiblings(this.items, item) || [];
+                const index = siblings.indexOf(item);
+                for (let i = index - 1; i >= 0 && siblings[i].type === 'radio'; i--) siblings[i].checked = false;
+                for (let i = index + 1; i < siblings.length && siblings[i].type === 'radio'; i++) siblings[i].checked = false;
+            }
+        }
+        const pane = win ? win.panes.get(msg.pane_id) : undefined;
+        if (item && typeof item.click === 'function') item.click(item, win, pane);
+        this.emit('activate', item || msg.item_id, win, pane);
+    }
+}
+
+function findSiblings(items, target) {
+    if (items.includes(target)) return items;
+    for (const item of items) {
+        if (item.submenu instanceof Menu) {
+            const found = findSiblings(item.submenu.items, target);
+            if (found) return found;
+        }
+    }
+    return null;
+}
+
+class Tray extends EventEmitter {
+    /**
+     * @param {object} options
+     * @param {Buffer|string} [options.icon] PNG data or path
+     * @param {string} [options.tooltip]
+     * @param {Array} [options.menu] Menu template, see setMenu()
+     */
+    constructor(options = {}) {
+        super();
+        ensureApp();
+        this.handle = new NativeTray(options.icon, options.tooltip);
+        this.id = this.handle.getId();
+        this._menuItems = new Map();
+        trays.set(this.id, this);
+        if (options.menu) this.setMenu(options.menu);
+    }
+
+    setIcon(icon) {
+        this.handle.setIcon(icon);
+    }
+
+    setTooltip(tooltip) {
+        this.handle.setTooltip(tooltip);
+    }
+
+    /** Replace the tray menu. Items with a `click` function have it called on activation. */
+    setMenu(template) {
+        this._menuItems = new Map();
+        this.handle.setMenu(buildMenuTemplate(template || [], this._menuItems));
+    }
+
+    destroy() {
+        this.handle.destroy();
+        trays.delete(this.id);
+    }
+
+    _handleEvent(msg) {
+        if (msg.event === 'tray-click') {
+            this.emit('click', { button: msg.button, x: msg.x, y: msg.y });
+        } else if (msg.event === 'tray-menu-activate') {
+            const item = this._menuItems.get(msg.item_id);
+            if (item && msg.checked !== null && msg.checked !== undefined) item.checked = msg.checked;
+            if (item && typeof item.click === 'function') item.click(item);
+            this.emit('menu-activate', msg.item_id, msg.checked);
+        } else if (msg.event === 'tray-error') {
+            this.emit('error', new Error(msg.message));
+        }
+    }
 }
 
+class Notification extends EventEmitter {
+    /**
+     * @param {object} options
+     * @param {string} options.title
+     * @param {string} [options.body]
+     * @param {string} [options.icon] PNG path (disk or VFS)
+     * @param {Array<{id: string, label: string}>} [options.actions]
+     * @param {boolean} [options.requireInteraction] Stay until dismissed
+     * @param {boolean} [options.silent]
+     */
+    constructor(options = {}) {
+        super();
+        this.options = options;
+        this.id = null;
+    }
+
+    show() {
+        // Events are delivered through the event loop.
+        ensureApp();
+        this.id = nativeShowNotification(this.options);
+        notifications.set(this.id, this);
+        return this;
+    }
+
+    close() {
+        if (this.id) nativeCloseNotification(this.id);
+    }
+
+    _handleEvent(msg) {
+        if (msg.event === 'notification-click') {
+            this.emit('click');
+        } else if (msg.event === 'notification-action') {
+            this.emit('action', msg.action_id);
+        } else if (msg.event === 'notification-close') {
+            notifications.delete(this.id);
+            this.emit('close', msg.reason);
+        } else if (msg.event === 'notification-error') {
+            notifications.delete(this.id);
+            this.emit('error', new Error(msg.message));
+        }
+    }
+}
+
+/** Show a notification right away; returns the Notification for its events. */
+function showNotification(options) {
+    return new Notification(options).show();
+}
+
+/** Accept (win, options) or just (options), like Electron. */
+function dialogArgs(win, options) {
+    return win instanceof ServoWindow ? [win, options] : [null, win];
+}
+
+/**
+ * Native dialogs. Passing a window makes the dialog modal to it: the window
+ * gets no input until the dialog closes.
+ */
+const dialog = {
+    showOpenDialog(win, options) {
+        [win, options] = dialogArgs(win, options);
+        return win ? win.handle.showOpenDialog(options) : nativeShowOpenDialog(options);
+    },
+
+    showSaveDialog(win, options) {
+        [win, options] = dialogArgs(win, options);
+        return win ? win.handle.showSaveDialog(options) : nativeShowSaveDialog(options);
+    },
+
+    showMessageBox(win, options) {
+        [win, options] = dialogArgs(win, options);
+        if (typeof options === 'string') options = { message: options };
+        return win ? win.handle.showMessageBox(options) : nativeShowMessageBox(options);
+    }
+};
+
 module.exports = {
     ServoWindow,
+    Menu,
+    MenuItem,
+    Tray,
+    dialog,
+    Notification,
+    showNotification,
     LayoutBuilder,
     Anchor,
+    FullscreenMode,
     ipcMain,
-    app: {
+    app: Object.assign(appEvents, {
         quit: () => globalApp && globalApp.quit(),
         warmup: ensureApp,
         initVfs: () => {
             ensureApp();
             if (globalApp) globalApp.initVfs();
+        },
+        getDisplays: () => {
+            ensureApp();
+            return globalApp ? globalApp.getDisplays() : [];
+        },
+        getPermissionDecisions: () => {
+            ensureApp();
+            return globalApp.getPermissionDecisions();
+        },
+        setPermissionDecision: (origin, permission, granted) => {
+            ensureApp();
+            globalApp.setPermissionDecision(origin, permission, granted === undefined ? null : granted);
+        },
+        clearPermissionDecisions: (origin) => {
+            ensureApp();
+            globalApp.clearPermissionDecisions(origin || null);
+        },
+        clearAuthCache: (origin) => {
+            if (globalApp) globalApp.clearAuthCache(origin || null);
+        },
+        openExternal: (url) => {
+            ensureApp();
+            globalApp.openExternal(url);
+        },
+        registerGlobalShortcut: async (accelerator, callback) => {
+            ensureApp();
+            await globalApp.registerGlobalShortcut(accelerator);
+            if (callback) globalShortcuts.set(accelerator, callback);
+        },
+        unregisterGlobalShortcut: async (accelerator) => {
+            globalShortcuts.delete(accelerator);
+            if (globalApp) await globalApp.unregisterGlobalShortcut(accelerator);
         }
-    }
+    })
 };
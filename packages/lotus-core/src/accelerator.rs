@@ -0,0 +1,123 @@
+use winit::keyboard::{Key, ModifiersState, NamedKey};
+
+/// A parsed Electron-style accelerator such as "CmdOrCtrl+Shift+S" or "Alt+F4".
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Accelerator {
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+    pub meta: bool,
+    /// Normalized key name: an uppercase character ("S", "1", "/") or a named
+    /// key as winit spells it ("F5", "Enter", "ArrowUp", "Space").
+    pub key: String,
+}
+
+impl Accelerator {
+    pub fn parse(accelerator: &str) -> Result<Self, String> {
+        let mut acc = Accelerator { ctrl: false, alt: false, shift: false, meta: false, key: String::new() };
+        let parts: Vec<&str> = accelerator.split('+').map(str::trim).collect();
+        // "Ctrl++" splits into [.., "", ""]: the trailing empty part is the plus key.
+        let (key, modifiers) = match parts.split_last() {
+            Some((&"", rest)) if rest.last() == Some(&"") => ("Plus", &rest[..rest.len() - 1]),
+            Some((key, rest)) => (*key, rest),
+            None => return Err(format!("Empty accelerator: {:?}", accelerator)),
+        };
+
+        for modifier in modifiers {
+            match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => acc.ctrl = true,
+                "alt" | "option" => acc.alt = true,
+                "shift" => acc.shift = true,
+                "super" | "meta" | "cmd" | "command" => acc.meta = true,
+                "cmdorctrl" | "commandorcontrol" => {
+                    if cfg!(target_os = "macos") { acc.meta = true } else { acc.ctrl = true }
+                }
+                other => return Err(format!("Unknown modifier {:?} in accelerator {:?}", other, accelerator)),
+            }
+        }
+
+        acc.key = normalize_key_name(key)
+            .ok_or_else(|| format!("Unknown key {:?} in accelerator {:?}", key, accelerator))?;
+        Ok(acc)
+    }
+
+    /// `key` should be the key without modifiers applied, so "Shift+1" matches
+    /// the "1" key rather than "!".
+    pub fn matches(&self, modifiers: ModifiersState, key: &Key) -> bool {
+        self.ctrl == modifiers.control_key()
+            && self.alt == modifiers.alt_key()
+            && self.shift == modifiers.shift_key()
+            && self.meta == modifiers.super_key()
+            && key_name(key).as_deref() == Some(self.key.as_str())
+    }
+
+    /// Key combination in the form com.canonical.dbusmenu's "shortcut" property uses.
+    pub fn dbus_shortcut(&self) -> Vec<String> {
+        let mut parts = Vec::new();
+        if self.ctrl { parts.push("Control".to_string()); }
+        if self.alt { parts.push("Alt".to_string()); }
+        if self.shift { parts.push("Shift".to_string()); }
+        if self.meta { parts.push("Super".to_string()); }
+        parts.push(match self.key.as_str() {
+            "Plus" => "plus".to_string(),
+            "Space" => "space".to_string(),
+            "Enter" => "Return".to_string(),
+            "ArrowUp" => "Up".to_string(),
+            "ArrowDown" => "Down".to_string(),
+            "ArrowLeft" => "Left".to_string(),
+            "ArrowRight" => "Right".to_string(),
+            key => key.to_string(),
+        });
+        parts
+    }
+}
+
+fn normalize_key_name(key: &str) -> Option<String> {
+    let mut chars = key.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(c.to_uppercase().collect());
+    }
+    let named = match key.to_ascii_lowercase().as_str() {
+        "plus" => "Plus",
+        "space" => "Space",
+        "tab" => "Tab",
+        "enter" | "return" => "Enter",
+        "esc" | "escape" => "Escape",
+        "backspace" => "Backspace",
+        "delete" | "del" => "Delete",
+        "insert" => "Insert",
+        "home" => "Home",
+        "end" => "End",
+        "pageup" => "PageUp",
+        "pagedown" => "PageDown",
+        "up" | "arrowup" => "ArrowUp",
+        "down" | "arrowdown" => "ArrowDown",
+        "left" | "arrowleft" => "ArrowLeft",
+        "right" | "arrowright" => "ArrowRight",
+        "printscreen" => "PrintScreen",
+        "mediaplaypause" => "MediaPlayPause",
+        "mediastop" => "MediaStop",
+        "medianexttrack" => "MediaTrackNext",
+        "mediaprevioustrack" => "MediaTrackPrevious",
+        "volumeup" => "AudioVolumeUp",
+        "volumedown" => "AudioVolumeDown",
+        "volumemute" => "AudioVolumeMute",
+        lower => {
+            // F1 - F24
+            let n: u8 = lower.strip_prefix('f')?.parse().ok()?;
+            return (1..=24).contains(&n).then(|| format!("F{}", n));
+        }
+    };
+    Some(named.to_string())
+}
+
+/// Name of a winit key in the same normalized form `Accelerator::key` uses.
+pub fn key_name(key: &Key) -> Option<String> {
+    match key {
+        Key::Character(c) if c.as_str() == "+" => Some("Plus".to_string()),
+        Key::Character(c) => Some(c.to_uppercase()),
+        Key::Named(NamedKey::Space) => Some("Space".to_string()),
+        Key::Named(named) => Some(format!("{:?}", named)),
+        _ => None,
+    }
+}
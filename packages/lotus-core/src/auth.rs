@@ -0,0 +1,121 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant};
+
+use servo::AuthenticationRequest;
+
+/// A URL challenged again this soon after it was sent cached credentials
+/// was refused them; the login is forgotten and Node asked instead of
+/// retrying it forever. Other URLs answered in the meantime don't count.
+const RETRY_GRACE: Duration = Duration::from_secs(5);
+
+/// What a remembered login answers: an origin's own challenges, or the
+/// proxy's, which are the same whatever origin the request was for.
+/// Servo doesn't pass on the challenge's realm, so an origin's realms share
+/// one login; a realm that refuses it evicts it like any failed attempt.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CredentialKey {
+    Origin(String),
+    Proxy,
+}
+
+impl CredentialKey {
+    pub fn new(origin: &str, for_proxy: bool) -> Self {
+        if for_proxy { CredentialKey::Proxy } else { CredentialKey::Origin(origin.to_string()) }
+    }
+}
+
+struct Credential {
+    username: String,
+    password: String,
+    // URLs recently answered with this login, and when.
+    attempts: HashMap<String, Instant>,
+}
+
+/// Logins kept for the rest of the session when Node asks to remember them.
+/// Never written to disk.
+#[derive(Default)]
+pub struct CredentialCache {
+    credentials: HashMap<CredentialKey, Credential>,
+}
+
+impl CredentialCache {
+    /// Credentials to answer a challenge for `url` with, unless this same
+    /// URL was just answered with them and challenged again.
+    pub fn lookup(&mut self, key: &CredentialKey, url: &str) -> Option<(String, String)> {
+        let now = Instant::now();
+        let credential = self.credentials.get_mut(key)?;
+        credential.attempts.retain(|_, sent| now.duration_since(*sent) < RETRY_GRACE);
+        if credential.attempts.contains_key(url) {
+            self.credentials.remove(key);
+            return None;
+        }
+        credential.attempts.insert(url.to_string(), now);
+        Some((credential.username.clone(), credential.password.clone()))
+    }
+
+    /// Remember a login Node just sent for `url`; if that URL is challenged
+    /// right back, it was wrong and is dropped.
+    pub fn insert(&mut self, key: CredentialKey, url: &str, username: &str, password: &str) {
+        self.credentials.insert(key, Credential {
+            username: username.to_string(),
+            password: password.to_string(),
+            attempts: HashMap::from([(url.to_string(), Instant::now())]),
+        });
+    }
+
+    /// Forget logins for one origin, or all of them (the proxy's included).
+    pub fn clear(&mut self, origin: Option<&str>) {
+        match origin {
+            Some(origin) => self.credentials.retain(|key, _| *key != CredentialKey::Origin(origin.to_string())),
+            None => self.credentials.clear(),
+        }
+    }
+}
+
+/// A challenge waiting on Node. Dropping it cancels the login.
+pub struct PendingLogin {
+    pub window_id: String,
+    pub key: CredentialKey,
+    pub url: String,
+    pub request: AuthenticationRequest,
+}
+
+// Servo's requests aren't Send; they wait here, on the event loop thread,
+// until Node answers through an engine command.
+thread_local! {
+    static PENDING: RefCell<HashMap<u32, PendingLogin>> = RefCell::new(HashMap::new());
+}
+
+static NEXT_SEQ: AtomicU32 = AtomicU32::new(1);
+
+pub fn hold(login: PendingLogin) -> u32 {
+    let seq = NEXT_SEQ.fetch_add(1, Ordering::Relaxed);
+    PENDING.with(|p| p.borrow_mut().insert(seq, login));
+    seq
+}
+
+pub fn take(window_id: &str, seq: u32) -> Option<PendingLogin> {
+    PENDING.with(|p| {
+        let mut pending = p.borrow_mut();
+        if pending.get(&seq).is_some_and(|l| l.window_id == window_id) {
+            pending.remove(&seq)
+        } else {
+            None
+        }
+    })
+}
+
+/// Cancel every login still waiting in a window (or everywhere, with None).
+pub fn discard(window_id: Option<&str>) {
+    let dropped: Vec<PendingLogin> = PENDING.with(|p| {
+        let mut pending = p.borrow_mut();
+        let seqs: Vec<u32> = pending.iter()
+            .filter(|(_, l)| window_id.map_or(true, |id| l.window_id == id))
+            .map(|(seq, _)| *seq)
+            .collect();
+        seqs.into_iter().filter_map(|seq| pending.remove(&seq)).collect()
+    });
+    drop(dropped);
+}
@@ -0,0 +1,65 @@
+use base64::Engine;
+
+/// What a pane does after its content process crashes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CrashPolicy {
+    /// Leave the pane as it is; Node decides.
+    #[default]
+    Keep,
+    Reload,
+    ShowErrorPage,
+}
+
+impl CrashPolicy {
+    pub fn parse(policy: &str) -> Result<Self, String> {
+        match policy {
+            "keep" => Ok(CrashPolicy::Keep),
+            "reload" => Ok(CrashPolicy::Reload),
+            "show-error-page" => Ok(CrashPolicy::ShowErrorPage),
+            other => Err(format!("Unknown crash policy '{}' (expected keep, reload or show-error-page)", other)),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CrashPolicy::Keep => "keep",
+            CrashPolicy::Reload => "reload",
+            CrashPolicy::ShowErrorPage => "show-error-page",
+        }
+    }
+}
+
+/// A pane that crashes again this soon after a reload gets the error page
+/// instead, so a page that crashes on load doesn't spin forever.
+pub const RELOAD_GRACE: std::time::Duration = std::time::Duration::from_secs(10);
+
+const ERROR_PAGE: &str = r#"<!DOCTYPE html>
+<html><head><meta charset="utf-8"><title>Page crashed</title>
+<style>
+body { font: 14px system-ui, sans-serif; color: #333; background: #f4f4f4; margin: 0; display: flex; align-items: center; justify-content: center; height: 100vh; }
+main { max-width: 40em; padding: 2em; }
+pre { white-space: pre-wrap; color: #666; font-size: 12px; }
+</style></head>
+<body><main><h1>This page crashed</h1><pre>{reason}</pre></main></body></html>
+"#;
+
+/// The built-in crash page as a data: URL.
+pub fn error_page_url(reason: &str) -> String {
+    let html = ERROR_PAGE.replace("{reason}", &escape_html(reason));
+    format!("data:text/html;charset=utf-8;base64,{}", base64::engine::general_purpose::STANDARD.encode(html))
+}
+
+pub fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,241 @@
+//! com.canonical.dbusmenu exporter shared by the tray (StatusNotifierItem menus)
+//! and window menu bars (global menu via the AppMenu registrar).
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use zbus::object_server::SignalContext;
+use zbus::zvariant::{OwnedValue, StructureBuilder, Type, Value};
+
+use crate::accelerator::Accelerator;
+use crate::menu::{self, MenuItemKind, MenuItemOptions};
+
+/// Called with (item_id, checked) when the host reports a click.
+pub type ActivateFn = Arc<dyn Fn(String, Option<bool>) + Send + Sync>;
+
+struct MenuNode {
+    path: Vec<usize>,
+    children: Vec<i32>,
+}
+
+pub struct MenuModel {
+    items: Vec<MenuItemOptions>,
+    // dbusmenu ids are indexes into `nodes`; 0 is the invisible root.
+    nodes: Vec<MenuNode>,
+    revision: u32,
+}
+
+impl MenuModel {
+    pub fn new() -> Self {
+        MenuModel {
+            items: Vec::new(),
+            nodes: vec![MenuNode { path: Vec::new(), children: Vec::new() }],
+            revision: 0,
+        }
+    }
+
+    fn set_items(&mut self, items: Vec<MenuItemOptions>) {
+        fn walk(items: &[MenuItemOptions], parent: usize, prefix: &[usize], nodes: &mut Vec<MenuNode>) {
+            for (i, item) in items.iter().enumerate() {
+                let mut path = prefix.to_vec();
+                path.push(i);
+                let id = nodes.len();
+                nodes.push(MenuNode { path: path.clone(), children: Vec::new() });
+                nodes[parent].children.push(id as i32);
+                walk(item.children(), id, &path, nodes);
+            }
+        }
+        let mut nodes = vec![MenuNode { path: Vec::new(), children: Vec::new() }];
+        walk(&items, 0, &[], &mut nodes);
+        self.items = items;
+        self.nodes = nodes;
+        self.revision += 1;
+    }
+
+    fn item(&self, id: i32) -> Option<&MenuItemOptions> {
+        let path = &self.nodes.get(id as usize)?.path;
+        let (first, rest) = path.split_first()?;
+        rest.iter().try_fold(self.items.get(*first)?, |item, &i| item.children().get(i))
+    }
+
+    /// Toggle checkbox/radio state for a clicked entry and return what to report.
+    fn activate(&mut self, id: i32) -> Option<(String, Option<bool>)> {
+        let path = self.nodes.get(id as usize)?.path.clone();
+        let (last, parents) = path.split_last()?;
+        let mut siblings = &mut self.items;
+        for &i in parents {
+            siblings = siblings.get_mut(i)?.submenu.as_mut()?;
+        }
+        let item_id = siblings.get(*last)?.item_id();
+        let checked = menu::toggle(siblings, *last);
+        if checked.is_some() {
+            self.revision += 1;
+        }
+        Some((item_id, checked))
+    }
+
+    fn properties(&self, id: i32) -> HashMap<String, Value<'static>> {
+        let mut props = HashMap::new();
+        if id == 0 {
+            props.insert("children-display".to_string(), Value::from("submenu".to_string()));
+            return props;
+        }
+        let Some(item) = self.item(id) else {
+            return props;
+        };
+        let kind = item.kind();
+        if kind == MenuItemKind::Separator {
+            props.insert("type".to_string(), Value::from("separator".to_string()));
+            return props;
+        }
+        if let Some(label) = &item.label {
+            props.insert("label".to_string(), Value::from(label.clone()));
+        }
+        if item.enabled == Some(false) {
+            props.insert("enabled".to_string(), Value::from(false));
+        }
+        if item.visible == Some(false) {
+            props.insert("visible".to_string(), Value::from(false));
+        }
+        if let Some(acc) = item.accelerator.as_deref().and_then(|a| Accelerator::parse(a).ok()) {
+            props.insert("shortcut".to_string(), Value::from(vec![acc.dbus_shortcut()]));
+        }
+        match kind {
+            MenuItemKind::Checkbox | MenuItemKind::Radio => {
+                let toggle = if kind == MenuItemKind::Checkbox { "checkmark" } else { "radio" };
+                props.insert("toggle-type".to_string(), Value::from(toggle.to_string()));
+                props.insert("toggle-state".to_string(), Value::from(item.checked.unwrap_or(false) as i32));
+            }
+            MenuItemKind::Submenu => {
+                props.insert("children-display".to_string(), Value::from("submenu".to_string()));
+            }
+            _ => {}
+        }
+        props
+    }
+
+    fn layout(&self, id: i32, depth: i32) -> LayoutItem {
+        let children = match self.nodes.get(id as usize) {
+            Some(node) if depth != 0 => node.children.iter()
+                .map(|&child| Value::from(self.layout(child, depth - 1)))
+                .collect(),
+            _ => Vec::new(),
+        };
+        LayoutItem { id, properties: self.properties(id), children }
+    }
+}
+
+/// dbusmenu layout node, signature (ia{sv}av). Children are nested as variants.
+#[derive(serde::Serialize, Type)]
+pub struct LayoutItem {
+    id: i32,
+    properties: HashMap<String, Value<'static>>,
+    children: Vec<Value<'static>>,
+}
+
+impl From<LayoutItem> for Value<'static> {
+    fn from(item: LayoutItem) -> Self {
+        Value::Structure(StructureBuilder::new()
+            .add_field(item.id)
+            .add_field(item.properties)
+            .add_field(item.children)
+            .build())
+    }
+}
+
+pub struct DbusMenu {
+    model: Arc<Mutex<MenuModel>>,
+    on_activate: ActivateFn,
+}
+
+impl DbusMenu {
+    pub fn new(model: Arc<Mutex<MenuModel>>, on_activate: ActivateFn) -> Self {
+        DbusMenu { model, on_activate }
+    }
+}
+
+#[zbus::interface(name = "com.canonical.dbusmenu")]
+impl DbusMenu {
+    fn get_layout(&self, parent_id: i32, recursion_depth: i32, _property_names: Vec<String>) -> (u32, LayoutItem) {
+        let model = self.model.lock().unwrap();
+        (model.revision, model.layout(parent_id, recursion_depth))
+    }
+
+    fn get_group_properties(&self, ids: Vec<i32>, _property_names: Vec<String>) -> Vec<(i32, HashMap<String, Value<'static>>)> {
+        let model = self.model.lock().unwrap();
+        let ids = if ids.is_empty() { (0..model.nodes.len() as i32).collect() } else { ids };
+        ids.into_iter().map(|id| (id, model.properties(id))).collect()
+    }
+
+    fn get_property(&self, id: i32, name: &str) -> zbus::fdo::Result<Value<'static>> {
+        self.model.lock().unwrap().properties(id).remove(name)
+            .ok_or_else(|| zbus::fdo::Error::InvalidArgs(format!("No property {} on item {}", name, id)))
+    }
+
+    async fn event(&self, id: i32, event_id: &str, _data: OwnedValue, _timestamp: u32, #[zbus(signal_context)] ctxt: SignalContext<'_>) {
+        if event_id != "clicked" {
+            return;
+        }
+        let (activated, revision) = {
+            let mut model = self.model.lock().unwrap();
+            let activated = model.activate(id);
+            (activated, model.revision)
+        };
+        if let Some((item_id, checked)) = activated {
+            if checked.is_some() {
+                let _ = Self::layout_updated(&ctxt, revision, 0).await;
+            }
+            (self.on_activate)(item_id, checked);
+        }
+    }
+
+    async fn event_group(&self, events: Vec<(i32, String, OwnedValue, u32)>, #[zbus(signal_context)] ctxt: SignalContext<'_>) -> Vec<i32> {
+        for (id, event_id, data, timestamp) in events {
+            self.event(id, &event_id, data, timestamp, ctxt.clone()).await;
+        }
+        Vec::new()
+    }
+
+    fn about_to_show(&self, _id: i32) -> bool {
+        false
+    }
+
+    fn about_to_show_group(&self, _ids: Vec<i32>) -> (Vec<i32>, Vec<i32>) {
+        (Vec::new(), Vec::new())
+    }
+
+    #[zbus(property)]
+    fn version(&self) -> u32 {
+        3
+    }
+
+    #[zbus(property)]
+    fn text_direction(&self) -> &str {
+        "ltr"
+    }
+
+    #[zbus(property)]
+    fn status(&self) -> &str {
+        "normal"
+    }
+
+    #[zbus(property)]
+    fn icon_theme_path(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    #[zbus(signal)]
+    async fn layout_updated(ctxt: &SignalContext<'_>, revision: u32, parent: i32) -> zbus::Result<()>;
+}
+
+/// Replace the menu served at `path` and tell the host to re-read it.
+pub fn set_items(conn: &zbus::blocking::Connection, path: &str, model: &Mutex<MenuModel>, items: Vec<MenuItemOptions>) {
+    let revision = {
+        let mut model = model.lock().unwrap();
+        model.set_items(items);
+        model.revision
+    };
+    if let Ok(iface) = conn.object_server().interface::<_, DbusMenu>(path) {
+        let _ = zbus::block_on(DbusMenu::layout_updated(iface.signal_context(), revision, 0));
+    }
+}
@@ -0,0 +1,629 @@
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+use napi_derive::napi;
+use winit::raw_window_handle::{
+    DisplayHandle, HandleError, HasDisplayHandle, HasWindowHandle, RawDisplayHandle, RawWindowHandle, WindowHandle,
+};
+use winit::window::Window;
+
+use crate::{EngineCommand, EVENT_LOOP_PROXY};
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct FileFilter {
+    pub name: String,
+    /// Extensions without the dot, e.g. ["png", "jpg"]. "*" matches everything.
+    pub extensions: Vec<String>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct OpenDialogOptions {
+    pub title: Option<String>,
+    pub default_path: Option<String>,
+    pub button_label: Option<String>,
+    pub filters: Option<Vec<FileFilter>>,
+    pub multi_selections: Option<bool>,
+    /// Pick folders instead of files.
+    pub directory: Option<bool>,
+}
+
+#[napi(object)]
+pub struct OpenDialogResult {
+    pub canceled: bool,
+    pub file_paths: Vec<String>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct SaveDialogOptions {
+    pub title: Option<String>,
+    pub default_path: Option<String>,
+    pub button_label: Option<String>,
+    pub filters: Option<Vec<FileFilter>>,
+}
+
+#[napi(object)]
+pub struct SaveDialogResult {
+    pub canceled: bool,
+    pub file_path: Option<String>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct MessageBoxOptions {
+    /// "none" (default), "info", "warning", "error" or "question".
+    #[napi(js_name = "type")]
+    pub kind: Option<String>,
+    pub title: Option<String>,
+    pub message: String,
+    pub detail: Option<String>,
+    /// Button labels, left to right. Defaults to ["OK"].
+    pub buttons: Option<Vec<String>>,
+    pub default_id: Option<u32>,
+    /// Reported when the box is dismissed without a button (Escape, window close).
+    pub cancel_id: Option<u32>,
+}
+
+#[napi(object)]
+pub struct MessageBoxResult {
+    /// Index of the clicked button.
+    pub response: u32,
+}
+
+impl MessageBoxOptions {
+    fn button_labels(&self) -> Vec<String> {
+        match &self.buttons {
+            Some(buttons) if !buttons.is_empty() => buttons.clone(),
+            _ => vec!["OK".to_string()],
+        }
+    }
+
+    fn cancel_index(&self) -> u32 {
+        // Like Electron: an explicit cancelId, else a button labelled Cancel/No, else 0.
+        self.cancel_id.unwrap_or_else(|| {
+            self.button_labels().iter()
+                .position(|b| matches!(b.to_ascii_lowercase().as_str(), "cancel" | "no"))
+                .unwrap_or(0) as u32
+        })
+    }
+}
+
+/// Raw handles of the window a dialog is parented to. The event loop defers
+/// closing that window until its dialogs have ended (see `EngineCommand::BeginDialog`
+/// and `DeferredClose`), so the handles stay valid while the dialog runs.
+#[derive(Debug, Clone, Copy)]
+pub struct DialogParent {
+    window: RawWindowHandle,
+    display: RawDisplayHandle,
+}
+
+// The handles are only passed to the platform dialog APIs, which expect to be
+// given another thread's window.
+unsafe impl Send for DialogParent {}
+
+impl DialogParent {
+    pub fn of(window: &Window) -> Option<Self> {
+        Some(DialogParent {
+            window: window.window_handle().ok()?.as_raw(),
+            display: window.display_handle().ok()?.as_raw(),
+        })
+    }
+
+    /// Parent identifier in the form xdg-desktop-portal expects ("" when unknown).
+    /// Wayland surfaces are exported through xdg-foreign; keep the export alive
+    /// until the dialog closes.
+    #[cfg(target_os = "linux")]
+    fn portal_handle(&self) -> (String, Option<crate::platform::WaylandExport>) {
+        match (self.window, self.display) {
+            (RawWindowHandle::Xlib(h), _) => (format!("x11:{:x}", h.window), None),
+            (RawWindowHandle::Xcb(h), _) => (format!("x11:{:x}", h.window.get()), None),
+            (RawWindowHandle::Wayland(w), RawDisplayHandle::Wayland(d)) => {
+                match crate::platform::export_wayland_surface(d.display.as_ptr(), w.surface.as_ptr()) {
+                    Some(export) => (format!("wayland:{}", export.handle), Some(export)),
+                    None => (String::new(), None),
+                }
+            }
+            _ => (String::new(), None),
+        }
+    }
+}
+
+impl HasWindowHandle for DialogParent {
+    fn window_handle(&self) -> Result<WindowHandle<'_>, HandleError> {
+        Ok(unsafe { WindowHandle::borrow_raw(self.window) })
+    }
+}
+
+impl HasDisplayHandle for DialogParent {
+    fn display_handle(&self) -> Result<DisplayHandle<'_>, HandleError> {
+        Ok(unsafe { DisplayHandle::borrow_raw(self.display) })
+    }
+}
+
+/// Run a dialog, blocking input to the parent window (if any) until it closes.
+async fn with_parent<T, F, Fut>(parent_id: Option<String>, run: F) -> napi::Result<T>
+where
+    F: FnOnce(Option<DialogParent>) -> Fut,
+    Fut: Future<Output = Result<T, String>>,
+{
+    let parent = match &parent_id {
+        Some(window_id) => {
+            let proxy = EVENT_LOOP_PROXY.get()
+                .ok_or_else(|| napi::Error::from_reason("Event loop is not running"))?;
+            let (tx, rx) = tokio::sync::oneshot::channel();
+            proxy.send_event(EngineCommand::BeginDialog(window_id.clone(), tx))
+                .map_err(|_| napi::Error::from_reason("Event loop has shut down"))?;
+            match rx.await {
+                Ok(Some(parent)) => Some(parent),
+                Ok(None) => return Err(napi::Error::from_reason(format!("Window {} not found", window_id))),
+                Err(_) => return Err(napi::Error::from_reason("Event loop dropped the request")),
+            }
+        }
+        None => None,
+    };
+
+    let result = run(parent).await;
+
+    if let (Some(window_id), Some(proxy)) = (parent_id, EVENT_LOOP_PROXY.get()) {
+        let _ = proxy.send_event(EngineCommand::EndDialog(window_id));
+    }
+    result.map_err(napi::Error::from_reason)
+}
+
+/// Split a default path into (folder, file name). An existing directory is all folder.
+fn split_default_path(path: Option<&str>) -> (Option<PathBuf>, Option<String>) {
+    let Some(path) = path.map(Path::new) else {
+        return (None, None);
+    };
+    if path.is_dir() {
+        return (Some(path.to_path_buf()), None);
+    }
+    let folder = path.parent().filter(|p| !p.as_os_str().is_empty()).map(Path::to_path_buf);
+    let name = path.file_name().map(|n| n.to_string_lossy().into_owned());
+    (folder, name)
+}
+
+fn path_strings(paths: Vec<PathBuf>) -> Vec<String> {
+    paths.into_iter().map(|p| p.to_string_lossy().into_owned()).collect()
+}
+
+pub async fn open(parent_id: Option<String>, options: OpenDialogOptions) -> napi::Result<OpenDialogResult> {
+    with_parent(parent_id, |parent| async move {
+        let paths = backend::open(parent, &options).await?;
+        Ok(match paths {
+            Some(paths) if !paths.is_empty() => OpenDialogResult { canceled: false, file_paths: path_strings(paths) },
+            _ => OpenDialogResult { canceled: true, file_paths: Vec::new() },
+        })
+    }).await
+}
+
+pub async fn save(parent_id: Option<String>, options: SaveDialogOptions) -> napi::Result<SaveDialogResult> {
+    with_parent(parent_id, |parent| async move {
+        let path = backend::save(parent, &options).await?;
+        Ok(SaveDialogResult {
+            canceled: path.is_none(),
+            file_path: path.map(|p| p.to_string_lossy().into_owned()),
+        })
+    }).await
+}
+
+pub async fn message_box(parent_id: Option<String>, options: MessageBoxOptions) -> napi::Result<MessageBoxResult> {
+    with_parent(parent_id, |parent| async move {
+        let response = backend::message_box(parent, &options).await?;
+        Ok(MessageBoxResult { response })
+    }).await
+}
+
+/// Open-file dialog without a parent window. Use `WindowHandle.showOpenDialog` for a modal one.
+#[napi(js_name = "showOpenDialog")]
+pub async fn show_open_dialog(options: Option<OpenDialogOptions>) -> napi::Result<OpenDialogResult> {
+    open(None, options.unwrap_or_default()).await
+}
+
+#[napi(js_name = "showSaveDialog")]
+pub async fn show_save_dialog(options: Option<SaveDialogOptions>) -> napi::Result<SaveDialogResult> {
+    save(None, options.unwrap_or_default()).await
+}
+
+#[napi(js_name = "showMessageBox")]
+pub async fn show_message_box(options: MessageBoxOptions) -> napi::Result<MessageBoxResult> {
+    message_box(None, options).await
+}
+
+/// Linux: the xdg-desktop-portal FileChooser (sandbox friendly, native look on
+/// every desktop), falling back to GTK when no portal is running. The portal has
+/// no message box, so those are always GTK.
+#[cfg(target_os = "linux")]
+mod backend {
+    use std::path::PathBuf;
+
+    use gtk::gdk;
+    use gtk::prelude::*;
+
+    use super::{portal, split_default_path, DialogParent, FileFilter, MessageBoxOptions, OpenDialogOptions, SaveDialogOptions};
+    use crate::platform;
+
+    extern "C" {
+        // libgdk-3; gtk-rs doesn't bind the backend-specific calls.
+        fn gdk_x11_window_foreign_new_for_display(display: *mut gdk::ffi::GdkDisplay, window: std::os::raw::c_ulong) -> *mut gdk::ffi::GdkWindow;
+        fn gdk_wayland_window_set_transient_for_exported(window: *mut gdk::ffi::GdkWindow, parent_handle_str: *mut std::os::raw::c_char) -> gtk::glib::ffi::gboolean;
+    }
+
+    /// Make a GTK dialog transient for the window behind a portal parent handle,
+    /// on whichever backend GDK picked. False if it couldn't be attached.
+    fn set_transient_parent(dialog: &impl IsA<gtk::Window>, parent_handle: &str) -> bool {
+        use gtk::glib::translate::{FromGlibPtrFull, ToGlibPtr};
+
+        let Some(display) = gdk::Display::default() else {
+            return false;
+        };
+        dialog.realize();
+        let Some(window) = dialog.window() else {
+            return false;
+        };
+        match (display.type_().name(), parent_handle.split_once(':')) {
+            ("GdkX11Display", Some(("x11", xid))) => {
+                let Ok(xid) = std::os::raw::c_ulong::from_str_radix(xid, 16) else {
+                    return false;
+                };
+                let parent = unsafe { gdk_x11_window_foreign_new_for_display(display.to_glib_none().0, xid) };
+                if parent.is_null() {
+                    return false;
+                }
+                window.set_transient_for(&unsafe { gdk::Window::from_glib_full(parent) });
+                true
+            }
+            ("GdkWaylandDisplay", Some(("wayland", handle))) => {
+                let Ok(handle) = std::ffi::CString::new(handle) else {
+                    return false;
+                };
+                unsafe { gdk_wayland_window_set_transient_for_exported(window.to_glib_none().0, handle.as_ptr() as *mut _) != 0 }
+            }
+            _ => false,
+        }
+    }
+
+    pub async fn open(parent: Option<DialogParent>, options: &OpenDialogOptions) -> Result<Option<Vec<PathBuf>>, String> {
+        let (parent_handle, _export) = parent.map(|p| p.portal_handle()).unwrap_or_default();
+        match portal::open_file(&parent_handle, options).await {
+            Err(portal::PortalError::Unavailable(e)) => {
+                log::debug!("Rust: File chooser portal unavailable ({}), using GTK", e);
+                let action = if options.directory == Some(true) { gtk::FileChooserAction::SelectFolder } else { gtk::FileChooserAction::Open };
+                gtk_file_chooser(action, parent_handle, options.title.clone(), options.button_label.clone(), options.default_path.clone(),
+                    options.filters.clone().unwrap_or_default(), options.multi_selections == Some(true)).await
+            }
+            Err(portal::PortalError::Failed(e)) => Err(e),
+            Ok(paths) => Ok(paths),
+        }
+    }
+
+    pub async fn save(parent: Option<DialogParent>, options: &SaveDialogOptions) -> Result<Option<PathBuf>, String> {
+        let (parent_handle, _export) = parent.map(|p| p.portal_handle()).unwrap_or_default();
+        let paths = match portal::save_file(&parent_handle, options).await {
+            Err(portal::PortalError::Unavailable(e)) => {
+                log::debug!("Rust: File chooser portal unavailable ({}), using GTK", e);
+                gtk_file_chooser(gtk::FileChooserAction::Save, parent_handle, options.title.clone(), options.button_label.clone(), options.default_path.clone(),
+                    options.filters.clone().unwrap_or_default(), false).await?
+            }
+            Err(portal::PortalError::Failed(e)) => return Err(e),
+            Ok(paths) => paths,
+        };
+        Ok(paths.and_then(|p| p.into_iter().next()))
+    }
+
+    async fn gtk_file_chooser(action: gtk::FileChooserAction, parent_handle: String, title: Option<String>, button_label: Option<String>, default_path: Option<String>, filters: Vec<FileFilter>, multiple: bool) -> Result<Option<Vec<PathBuf>>, String> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let started = platform::run_on_gtk_thread(move || {
+            let accept = button_label.unwrap_or_else(|| match action {
+                gtk::FileChooserAction::Save => "_Save".to_string(),
+                gtk::FileChooserAction::SelectFolder => "_Select".to_string(),
+                _ => "_Open".to_string(),
+            });
+            let dialog = gtk::FileChooserDialog::with_buttons(
+                title.as_deref(),
+                None::<&gtk::Window>,
+                action,
+                &[("_Cancel", gtk::ResponseType::Cancel), (accept.as_str(), gtk::ResponseType::Accept)],
+            );
+            dialog.set_select_multiple(multiple);
+            dialog.set_do_overwrite_confirmation(true);
+            dialog.set_modal(true);
+            if !set_transient_parent(&dialog, &parent_handle) {
+                // No parent to attach to, so at least keep it over the window.
+                dialog.set_keep_above(true);
+            }
+            for filter in &filters {
+                let gtk_filter = gtk::FileFilter::new();
+                gtk_filter.set_name(Some(&filter.name));
+                for ext in &filter.extensions {
+                    gtk_filter.add_pattern(&if ext == "*" { "*".to_string() } else { format!("*.{}", ext) });
+                }
+                dialog.add_filter(gtk_filter);
+            }
+            let (folder, name) = split_default_path(default_path.as_deref());
+            if let Some(folder) = folder {
+                dialog.set_current_folder(folder);
+            }
+            if let Some(name) = name {
+                if action == gtk::FileChooserAction::Save {
+                    dialog.set_current_name(&name);
+                }
+            }
+
+            let response = dialog.run();
+            let paths = (response == gtk::ResponseType::Accept).then(|| dialog.filenames());
+            dialog.close();
+            let _ = tx.send(paths);
+        });
+        if !started {
+            return Err("No file dialog available: no xdg-desktop-portal and GTK could not be initialized".to_string());
+        }
+        rx.await.map_err(|_| "The file dialog was closed unexpectedly".to_string())
+    }
+
+    pub async fn message_box(parent: Option<DialogParent>, options: &MessageBoxOptions) -> Result<u32, String> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let options = options.clone();
+        let (parent_handle, _export) = parent.map(|p| p.portal_handle()).unwrap_or_default();
+        let started = platform::run_on_gtk_thread(move || {
+            let message_type = match options.kind.as_deref() {
+                Some("info") => gtk::MessageType::Info,
+                Some("warning") => gtk::MessageType::Warning,
+                Some("error") => gtk::MessageType::Error,
+                Some("question") => gtk::MessageType::Question,
+                _ => gtk::MessageType::Other,
+            };
+            let dialog = gtk::MessageDialog::new(None::<&gtk::Window>, gtk::DialogFlags::MODAL, message_type, gtk::ButtonsType::None, &options.message);
+            if let Some(title) = &options.title {
+                dialog.set_title(title);
+            }
+            dialog.set_secondary_text(options.detail.as_deref());
+            if !set_transient_parent(&dialog, &parent_handle) {
+                dialog.set_keep_above(true);
+            }
+            for (i, label) in options.button_labels().iter().enumerate() {
+                dialog.add_button(label, gtk::ResponseType::Other(i as u16));
+            }
+            if let Some(default_id) = options.default_id {
+                dialog.set_default_response(gtk::ResponseType::Other(default_id as u16));
+            }
+
+            let response = match dialog.run() {
+                gtk::ResponseType::Other(i) => i as u32,
+                _ => options.cancel_index(),
+            };
+            dialog.close();
+            let _ = tx.send(response);
+        });
+        if !started {
+            return Err("No message box available: GTK could not be initialized".to_string());
+        }
+        rx.await.map_err(|_| "The message box was closed unexpectedly".to_string())
+    }
+}
+
+/// org.freedesktop.portal.FileChooser. Requests are answered asynchronously
+/// through a Response signal on a per-request object.
+#[cfg(target_os = "linux")]
+mod portal {
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use futures_util::StreamExt;
+    use zbus::zvariant::{OwnedValue, Value};
+
+    use super::{split_default_path, FileFilter, OpenDialogOptions, SaveDialogOptions};
+
+    const DESKTOP: &str = "org.freedesktop.portal.Desktop";
+    const DESKTOP_PATH: &str = "/org/freedesktop/portal/desktop";
+    const FILE_CHOOSER: &str = "org.freedesktop.portal.FileChooser";
+
+    static NEXT_TOKEN: AtomicU32 = AtomicU32::new(1);
+
+    pub enum PortalError {
+        /// No portal to talk to; the caller should fall back.
+        Unavailable(String),
+        /// The portal ran but the request failed.
+        Failed(String),
+    }
+
+    fn filters_value(filters: &[FileFilter]) -> Value<'static> {
+        // a(sa(us)): 0 = glob pattern
+        let filters: Vec<(String, Vec<(u32, String)>)> = filters.iter().map(|f| {
+            let patterns = f.extensions.iter()
+                .map(|ext| (0u32, if ext == "*" { "*".to_string() } else { format!("*.{}", ext) }))
+                .collect();
+            (f.name.clone(), patterns)
+        }).collect();
+        Value::from(filters)
+    }
+
+    fn path_bytes(path: &std::path::Path) -> Value<'static> {
+        use std::os::unix::ffi::OsStrExt;
+        let mut bytes = path.as_os_str().as_bytes().to_vec();
+        bytes.push(0);
+        Value::from(bytes)
+    }
+
+    pub async fn open_file(parent_window: &str, options: &OpenDialogOptions) -> Result<Option<Vec<PathBuf>>, PortalError> {
+        let mut args: HashMap<&str, Value> = HashMap::new();
+        args.insert("modal", Value::from(true));
+        args.insert("multiple", Value::from(options.multi_selections == Some(true)));
+        args.insert("directory", Value::from(options.directory == Some(true)));
+        if let Some(label) = &options.button_label {
+            args.insert("accept_label", Value::from(label.clone()));
+        }
+        if let Some(filters) = options.filters.as_deref().filter(|f| !f.is_empty()) {
+            args.insert("filters", filters_value(filters));
+        }
+        if let (Some(folder), _) = split_default_path(options.default_path.as_deref()) {
+            args.insert("current_folder", path_bytes(&folder));
+        }
+        let title = options.title.clone().unwrap_or_else(|| "Open".to_string());
+        request("OpenFile", parent_window, &title, args).await
+    }
+
+    pub async fn save_file(parent_window: &str, options: &SaveDialogOptions) -> Result<Option<Vec<PathBuf>>, PortalError> {
+        let mut args: HashMap<&str, Value> = HashMap::new();
+        args.insert("modal", Value::from(true));
+        if let Some(label) = &options.button_label {
+            args.insert("accept_label", Value::from(label.clone()));
+        }
+        if let Some(filters) = options.filters.as_deref().filter(|f| !f.is_empty()) {
+            args.insert("filters", filters_value(filters));
+        }
+        let (folder, name) = split_default_path(options.default_path.as_deref());
+        if let Some(folder) = folder {
+            args.insert("current_folder", path_bytes(&folder));
+        }
+        if let Some(name) = name {
+            args.insert("current_name", Value::from(name));
+        }
+        let title = options.title.clone().unwrap_or_else(|| "Save".to_string());
+        request("SaveFile", parent_window, &title, args).await
+    }
+
+    async fn request(method: &str, parent_window: &str, title: &str, mut args: HashMap<&str, Value<'_>>) -> Result<Option<Vec<PathBuf>>, PortalError> {
+        let unavailable = |e: zbus::Error| PortalError::Unavailable(e.to_string());
+        let conn = zbus::Connection::session().await.map_err(unavailable)?;
+
+        // Subscribe to the request's Response before making the call so a fast
+        // answer can't be missed. The object path is derived from our token.
+        let token = format!("lotus{}", NEXT_TOKEN.fetch_add(1, Ordering::Relaxed));
+        let sender = conn.unique_name()
+            .map(|n| n.trim_start_matches(':').replace('.', "_"))
+            .ok_or_else(|| PortalError::Unavailable("no unique bus name".to_string()))?;
+        let request_path = format!("{}/request/{}/{}", DESKTOP_PATH, sender, token);
+        let request = zbus::Proxy::new(&conn, DESKTOP, request_path, "org.freedesktop.portal.Request").await.map_err(unavailable)?;
+        let mut responses = request.receive_signal("Response").await.map_err(unavailable)?;
+
+        args.insert("handle_token", Value::from(token));
+        let chooser = zbus::Proxy::new(&conn, DESKTOP, DESKTOP_PATH, FILE_CHOOSER).await.map_err(unavailable)?;
+        chooser.call_method(method, &(parent_window, title, args)).await.map_err(unavailable)?;
+
+        let response = responses.next().await
+            .ok_or_else(|| PortalError::Failed("The file chooser portal closed the request".to_string()))?;
+        let (code, mut results): (u32, HashMap<String, OwnedValue>) = response.body().deserialize()
+            .map_err(|e| PortalError::Failed(e.to_string()))?;
+        match code {
+            0 => {}
+            1 => return Ok(None),
+            _ => return Err(PortalError::Failed("The file chooser portal reported an error".to_string())),
+        }
+
+        let uris = results.remove("uris")
+            .and_then(|v| Vec::<String>::try_from(v).ok())
+            .unwrap_or_default();
+        Ok(Some(uris.iter().filter_map(|uri| url::Url::parse(uri).ok()?.to_file_path().ok()).collect()))
+    }
+}
+
+/// Windows and macOS: the system dialogs through rfd.
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+mod backend {
+    use std::path::PathBuf;
+
+    use rfd::{AsyncFileDialog, AsyncMessageDialog, MessageButtons, MessageDialogResult, MessageLevel};
+
+    use super::{split_default_path, DialogParent, FileFilter, MessageBoxOptions, OpenDialogOptions, SaveDialogOptions};
+
+    fn file_dialog(parent: Option<DialogParent>, title: &Option<String>, default_path: &Option<String>, filters: &Option<Vec<FileFilter>>) -> AsyncFileDialog {
+        let mut dialog = AsyncFileDialog::new();
+        if let Some(parent) = &parent {
+            dialog = dialog.set_parent(parent);
+        }
+        if let Some(title) = title {
+            dialog = dialog.set_title(title);
+        }
+        let (folder, name) = split_default_path(default_path.as_deref());
+        if let Some(folder) = folder {
+            dialog = dialog.set_directory(folder);
+        }
+        if let Some(name) = name {
+            dialog = dialog.set_file_name(name);
+        }
+        for filter in filters.iter().flatten() {
+            dialog = dialog.add_filter(&filter.name, &filter.extensions);
+        }
+        dialog
+    }
+
+    pub async fn open(parent: Option<DialogParent>, options: &OpenDialogOptions) -> Result<Option<Vec<PathBuf>>, String> {
+        let dialog = file_dialog(parent, &options.title, &options.default_path, &options.filters);
+        let multiple = options.multi_selections == Some(true);
+        let handles = match (options.directory == Some(true), multiple) {
+            (true, true) => dialog.pick_folders().await,
+            (true, false) => dialog.pick_folder().await.map(|h| vec![h]),
+            (false, true) => dialog.pick_files().await,
+            (false, false) => dialog.pick_file().await.map(|h| vec![h]),
+        };
+        Ok(handles.map(|handles| handles.iter().map(|h| h.path().to_path_buf()).collect()))
+    }
+
+    pub async fn save(parent: Option<DialogParent>, options: &SaveDialogOptions) -> Result<Option<PathBuf>, String> {
+        let dialog = file_dialog(parent, &options.title, &options.default_path, &options.filters);
+        Ok(dialog.save_file().await.map(|h| h.path().to_path_buf()))
+    }
+
+    pub async fn message_box(parent: Option<DialogParent>, options: &MessageBoxOptions) -> Result<u32, String> {
+        let labels = options.button_labels();
+        // rfd offers at most three custom buttons.
+        if labels.len() > 3 {
+            log::warn!("Rust: Message box supports up to 3 buttons, ignoring {:?}", &labels[3..]);
+        }
+        let buttons = match labels.as_slice() {
+            [ok] => MessageButtons::OkCustom(ok.clone()),
+            [ok, cancel] => MessageButtons::OkCancelCustom(ok.clone(), cancel.clone()),
+            [yes, no, cancel, ..] => MessageButtons::YesNoCancelCustom(yes.clone(), no.clone(), cancel.clone()),
+            [] => MessageButtons::Ok,
+        };
+        let level = match options.kind.as_deref() {
+            Some("warning") => MessageLevel::Warning,
+            Some("error") => MessageLevel::Error,
+            _ => MessageLevel::Info,
+        };
+        let mut dialog = AsyncMessageDialog::new()
+            .set_level(level)
+            .set_title(options.title.clone().unwrap_or_default())
+            .set_buttons(buttons);
+        dialog = match &options.detail {
+            Some(detail) => dialog.set_description(format!("{}\n\n{}", options.message, detail)),
+            None => dialog.set_description(&options.message),
+        };
+        if let Some(parent) = &parent {
+            dialog = dialog.set_parent(parent);
+        }
+
+        let index_of = |label: &str| labels.iter().position(|l| l == label).map(|i| i as u32);
+        Ok(match dialog.show().await {
+            MessageDialogResult::Custom(label) => index_of(&label).unwrap_or_else(|| options.cancel_index()),
+            MessageDialogResult::Ok | MessageDialogResult::Yes => 0,
+            MessageDialogResult::No => 1,
+            MessageDialogResult::Cancel => options.cancel_index(),
+        })
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+mod backend {
+    use std::path::PathBuf;
+
+    use super::{DialogParent, MessageBoxOptions, OpenDialogOptions, SaveDialogOptions};
+
+    const UNSUPPORTED: &str = "Native dialogs are not supported on this platform yet";
+
+    pub async fn open(_parent: Option<DialogParent>, _options: &OpenDialogOptions) -> Result<Option<Vec<PathBuf>>, String> {
+        Err(UNSUPPORTED.to_string())
+    }
+
+    pub async fn save(_parent: Option<DialogParent>, _options: &SaveDialogOptions) -> Result<Option<PathBuf>, String> {
+        Err(UNSUPPORTED.to_string())
+    }
+
+    pub async fn message_box(_parent: Option<DialogParent>, _options: &MessageBoxOptions) -> Result<u32, String> {
+        Err(UNSUPPORTED.to_string())
+    }
+}
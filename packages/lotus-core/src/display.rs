@@ -0,0 +1,117 @@
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use winit::event_loop::ActiveEventLoop;
+use winit::monitor::MonitorHandle;
+
+use crate::platform;
+
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisplayBounds {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl DisplayBounds {
+    /// Area (in physical pixels) shared between this rect and another.
+    pub fn intersection_area(&self, other: &DisplayBounds) -> u64 {
+        let left = self.x.max(other.x);
+        let top = self.y.max(other.y);
+        let right = (self.x + self.width as i32).min(other.x + other.width as i32);
+        let bottom = (self.y + self.height as i32).min(other.y + other.height as i32);
+        if right <= left || bottom <= top {
+            return 0;
+        }
+        (right - left) as u64 * (bottom - top) as u64
+    }
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisplayInfo {
+    /// Stable identifier: the OS output name (e.g. "DP-1", "\\.\DISPLAY1") when available.
+    pub id: String,
+    pub name: Option<String>,
+    /// Full monitor rect in physical desktop coordinates.
+    pub bounds: DisplayBounds,
+    /// Monitor rect minus panels/taskbars. Equal to `bounds` where the platform can't tell.
+    pub work_area: DisplayBounds,
+    pub scale_factor: f64,
+    /// Refresh rate in Hz, if reported.
+    pub refresh_rate: Option<f64>,
+    pub is_primary: bool,
+}
+
+pub fn display_id(monitor: &MonitorHandle) -> String {
+    match monitor.name() {
+        Some(name) => name,
+        None => {
+            let pos = monitor.position();
+            format!("display-{}-{}", pos.x, pos.y)
+        }
+    }
+}
+
+/// Snapshot every monitor the event loop currently knows about.
+pub fn enumerate(event_loop: &ActiveEventLoop) -> Vec<DisplayInfo> {
+    let primary = event_loop.primary_monitor();
+    let monitors: Vec<MonitorHandle> = event_loop.available_monitors().collect();
+    let work_areas = platform::work_areas(&monitors);
+
+    monitors.iter().zip(work_areas).map(|(monitor, work_area)| {
+        let pos = monitor.position();
+        let size = monitor.size();
+        let bounds = DisplayBounds {
+            x: pos.x,
+            y: pos.y,
+            width: size.width,
+            height: size.height,
+        };
+        DisplayInfo {
+            id: display_id(monitor),
+            name: monitor.name(),
+            work_area: work_area.unwrap_or_else(|| bounds.clone()),
+            bounds,
+            scale_factor: monitor.scale_factor(),
+            refresh_rate: monitor.refresh_rate_millihertz().map(|mhz| mhz as f64 / 1000.0),
+            is_primary: primary.as_ref() == Some(monitor),
+        }
+    }).collect()
+}
+
+pub enum DisplayChange {
+    Added(DisplayInfo),
+    Removed(DisplayInfo),
+    Changed(DisplayInfo),
+}
+
+/// Compare two snapshots by display id.
+pub fn diff(old: &[DisplayInfo], new: &[DisplayInfo]) -> Vec<DisplayChange> {
+    let mut changes = Vec::new();
+    for d in new {
+        match old.iter().find(|o| o.id == d.id) {
+            None => changes.push(DisplayChange::Added(d.clone())),
+            Some(o) if o != d => changes.push(DisplayChange::Changed(d.clone())),
+            _ => {}
+        }
+    }
+    for o in old {
+        if !new.iter().any(|d| d.id == o.id) {
+            changes.push(DisplayChange::Removed(o.clone()));
+        }
+    }
+    changes
+}
+
+/// Top-left position that centers a window of `size` inside the display's work area.
+pub fn centered_position(display: &DisplayInfo, width: u32, height: u32) -> winit::dpi::PhysicalPosition<i32> {
+    let wa = &display.work_area;
+    winit::dpi::PhysicalPosition::new(
+        wa.x + (wa.width as i32 - width as i32) / 2,
+        wa.y + (wa.height as i32 - height as i32) / 2,
+    )
+}
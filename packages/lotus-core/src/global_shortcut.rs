@@ -0,0 +1,529 @@
+use tokio::sync::oneshot;
+use winit::event_loop::ActiveEventLoop;
+
+use crate::accelerator::Accelerator;
+
+pub type Reply = oneshot::Sender<Result<(), String>>;
+
+#[derive(Debug)]
+pub struct ShortcutEvent {
+    pub accelerator: String,
+    pub pressed: bool,
+}
+
+impl ShortcutEvent {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "event": "global-shortcut",
+            "accelerator": self.accelerator,
+            "state": if self.pressed { "pressed" } else { "released" }
+        })
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn send_event(accelerator: &str, pressed: bool) {
+    if let Some(proxy) = crate::EVENT_LOOP_PROXY.get() {
+        let _ = proxy.send_event(crate::EngineCommand::GlobalShortcut(ShortcutEvent {
+            accelerator: accelerator.to_string(),
+            pressed,
+        }));
+    }
+}
+
+/// System-wide shortcuts, owned by the event loop. The platform backend is picked
+/// on first use, once we know whether the event loop runs on X11 or Wayland.
+#[derive(Default)]
+pub struct GlobalShortcuts {
+    backend: Option<backend::Backend>,
+}
+
+impl GlobalShortcuts {
+    fn backend(&mut self, event_loop: &ActiveEventLoop) -> Result<&mut backend::Backend, String> {
+        if self.backend.is_none() {
+            self.backend = Some(backend::Backend::new(event_loop)?);
+        }
+        Ok(self.backend.as_mut().unwrap())
+    }
+
+    pub fn register(&mut self, event_loop: &ActiveEventLoop, accelerator: String, reply: Reply) {
+        let parsed = match Accelerator::parse(&accelerator) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                let _ = reply.send(Err(e));
+                return;
+            }
+        };
+        match self.backend(event_loop) {
+            Ok(backend) => backend.register(parsed, accelerator, reply),
+            Err(e) => {
+                let _ = reply.send(Err(e));
+            }
+        }
+    }
+
+    pub fn unregister(&mut self, accelerator: &str) -> Result<(), String> {
+        let parsed = Accelerator::parse(accelerator)?;
+        match self.backend.as_mut() {
+            Some(backend) if backend.unregister(&parsed) => Ok(()),
+            _ => Err(format!("{} is not registered", accelerator)),
+        }
+    }
+
+    /// Key events the backend has queued since the last call (X11 only).
+    pub fn drain(&mut self) -> Vec<ShortcutEvent> {
+        self.backend.as_mut().map(|b| b.drain()).unwrap_or_default()
+    }
+
+    /// Handle a wake-up from the X11 watcher: read what arrived and let it poll again.
+    pub fn ready(&mut self) -> Vec<ShortcutEvent> {
+        let events = self.drain();
+        if let Some(backend) = self.backend.as_mut() {
+            backend.ack();
+        }
+        events
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod backend {
+    use winit::event_loop::ActiveEventLoop;
+    use winit::platform::wayland::ActiveEventLoopExtWayland;
+
+    use super::{portal, x11, Reply, ShortcutEvent};
+    use crate::accelerator::Accelerator;
+
+    pub enum Backend {
+        X11(x11::Grabs),
+        Portal(portal::Shortcuts),
+    }
+
+    impl Backend {
+        pub fn new(event_loop: &ActiveEventLoop) -> Result<Self, String> {
+            if event_loop.is_wayland() {
+                portal::Shortcuts::new().map(Backend::Portal)
+            } else {
+                x11::Grabs::new().map(Backend::X11)
+            }
+        }
+
+        pub fn register(&mut self, parsed: Accelerator, accelerator: String, reply: Reply) {
+            match self {
+                Backend::X11(grabs) => {
+                    let _ = reply.send(grabs.register(parsed, accelerator));
+                }
+                Backend::Portal(shortcuts) => shortcuts.register(parsed, accelerator, reply),
+            }
+        }
+
+        pub fn unregister(&mut self, parsed: &Accelerator) -> bool {
+            match self {
+                Backend::X11(grabs) => grabs.unregister(parsed),
+                Backend::Portal(shortcuts) => shortcuts.unregister(parsed),
+            }
+        }
+
+        pub fn drain(&mut self) -> Vec<ShortcutEvent> {
+            match self {
+                Backend::X11(grabs) => grabs.drain(),
+                Backend::Portal(_) => Vec::new(),
+            }
+        }
+
+        pub fn ack(&mut self) {
+            if let Backend::X11(grabs) = self {
+                grabs.ack();
+            }
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod backend {
+    use winit::event_loop::ActiveEventLoop;
+
+    use super::{Reply, ShortcutEvent};
+    use crate::accelerator::Accelerator;
+
+    pub struct Backend;
+
+    impl Backend {
+        pub fn new(_event_loop: &ActiveEventLoop) -> Result<Self, String> {
+            Err("Global shortcuts are not supported on this platform yet".to_string())
+        }
+        pub fn register(&mut self, _parsed: Accelerator, _accelerator: String, _reply: Reply) {}
+        pub fn unregister(&mut self, _parsed: &Accelerator) -> bool {
+            false
+        }
+        pub fn drain(&mut self) -> Vec<ShortcutEvent> {
+            Vec::new()
+        }
+        pub fn ack(&mut self) {}
+    }
+}
+
+/// XGrabKey on the root window, on a connection of our own so no other helper
+/// swallows its key events. A watcher thread only waits for the socket to become
+/// readable and wakes the event loop, which reads and dispatches the events.
+#[cfg(target_os = "linux")]
+mod x11 {
+    use std::collections::HashMap;
+    use std::os::fd::AsRawFd;
+    use std::sync::mpsc;
+
+    use x11rb::connection::Connection;
+    use x11rb::protocol::xproto::{ConnectionExt as _, GrabMode, ModMask};
+    use x11rb::protocol::Event;
+    use x11rb::rust_connection::RustConnection;
+
+    use super::ShortcutEvent;
+    use crate::accelerator::Accelerator;
+
+    const SHIFT: u16 = 1;
+    const LOCK: u16 = 2;
+    const CONTROL: u16 = 4;
+    const MOD1: u16 = 8; // Alt
+    const MOD2: u16 = 16; // NumLock
+    const MOD4: u16 = 64; // Super
+    const RELEVANT: u16 = SHIFT | CONTROL | MOD1 | MOD4;
+
+    struct Grab {
+        accelerator: String,
+        keycode: u8,
+        modifiers: u16,
+    }
+
+    pub struct Grabs {
+        conn: RustConnection,
+        root: u32,
+        grabs: HashMap<Accelerator, Grab>,
+        ack: mpsc::Sender<()>,
+    }
+
+    impl Grabs {
+        pub fn new() -> Result<Self, String> {
+            let (conn, screen) = x11rb::connect(None).map_err(|e| format!("Cannot connect to the X server: {}", e))?;
+            let root = conn.setup().roots[screen].root;
+            let fd = conn.stream().as_raw_fd();
+            let (ack, acked) = mpsc::channel::<()>();
+
+            std::thread::spawn(move || loop {
+                let mut pfd = libc::pollfd { fd, events: libc::POLLIN, revents: 0 };
+                if unsafe { libc::poll(&mut pfd, 1, -1) } < 0 || pfd.revents & (libc::POLLERR | libc::POLLHUP) != 0 {
+                    break;
+                }
+                let Some(proxy) = crate::EVENT_LOOP_PROXY.get() else { break };
+                if proxy.send_event(crate::EngineCommand::GlobalShortcutsReady).is_err() {
+                    break;
+                }
+                // Wait until the event loop has read the socket before polling again.
+                if acked.recv().is_err() {
+                    break;
+                }
+            });
+
+            Ok(Grabs { conn, root, grabs: HashMap::new(), ack })
+        }
+
+        fn keycode_for(&self, keysym: u32) -> Option<u8> {
+            let setup = self.conn.setup();
+            let (min, max) = (setup.min_keycode, setup.max_keycode);
+            let mapping = self.conn.get_keyboard_mapping(min, max - min + 1).ok()?.reply().ok()?;
+            let per_keycode = mapping.keysyms_per_keycode.max(1) as usize;
+            mapping.keysyms.chunks(per_keycode)
+                .position(|syms| syms.contains(&keysym))
+                .map(|i| min + i as u8)
+        }
+
+        pub fn register(&mut self, parsed: Accelerator, accelerator: String) -> Result<(), String> {
+            if self.grabs.contains_key(&parsed) {
+                return Err(format!("{} is already registered", accelerator));
+            }
+            let keysym = keysym(&parsed.key).ok_or_else(|| format!("{} can't be grabbed on X11", accelerator))?;
+            let keycode = self.keycode_for(keysym)
+                .ok_or_else(|| format!("No key on the current keyboard layout produces {}", accelerator))?;
+            let mut modifiers = 0;
+            if parsed.shift { modifiers |= SHIFT; }
+            if parsed.ctrl { modifiers |= CONTROL; }
+            if parsed.alt { modifiers |= MOD1; }
+            if parsed.meta { modifiers |= MOD4; }
+
+            // Grab with every NumLock/CapsLock combination, or the shortcut stops
+            // working whenever one of them is on.
+            for (i, extra) in [0, LOCK, MOD2, LOCK | MOD2].into_iter().enumerate() {
+                let result = self.conn
+                    .grab_key(true, self.root, ModMask::from(modifiers | extra), keycode, GrabMode::ASYNC, GrabMode::ASYNC)
+                    .map_err(|e| e.to_string())
+                    .and_then(|cookie| cookie.check().map_err(|e| e.to_string()));
+                if let Err(e) = result {
+                    for undo in [0, LOCK, MOD2, LOCK | MOD2].into_iter().take(i) {
+                        let _ = self.conn.ungrab_key(keycode, self.root, ModMask::from(modifiers | undo));
+                    }
+                    let _ = self.conn.flush();
+                    return Err(format!("{} is already taken by another application ({})", accelerator, e));
+                }
+            }
+            let _ = self.conn.flush();
+            self.grabs.insert(parsed, Grab { accelerator, keycode, modifiers });
+            Ok(())
+        }
+
+        pub fn unregister(&mut self, parsed: &Accelerator) -> bool {
+            let Some(grab) = self.grabs.remove(parsed) else { return false };
+            for extra in [0, LOCK, MOD2, LOCK | MOD2] {
+                let _ = self.conn.ungrab_key(grab.keycode, self.root, ModMask::from(grab.modifiers | extra));
+            }
+            let _ = self.conn.flush();
+            true
+        }
+
+        pub fn ack(&self) {
+            let _ = self.ack.send(());
+        }
+
+        fn find(&self, keycode: u8, state: u16) -> Option<&Grab> {
+            self.grabs.values().find(|g| g.keycode == keycode && g.modifiers == state & RELEVANT)
+        }
+
+        pub fn drain(&mut self) -> Vec<ShortcutEvent> {
+            let mut keys = Vec::new(); // (pressed, keycode, state, time)
+            while let Ok(Some(event)) = self.conn.poll_for_event() {
+                match event {
+                    Event::KeyPress(e) => keys.push((true, e.detail, u16::from(e.state), e.time)),
+                    Event::KeyRelease(e) => keys.push((false, e.detail, u16::from(e.state), e.time)),
+                    _ => {}
+                }
+            }
+
+            // Auto-repeat arrives as release + press with the same timestamp; a held
+            // key should read as one press and one release.
+            let mut events = Vec::new();
+            let mut i = 0;
+            while i < keys.len() {
+                let (pressed, keycode, state, time) = keys[i];
+                let repeat = !pressed && keys.get(i + 1).is_some_and(|&(p, k, _, t)| p && k == keycode && t == time);
+                if repeat {
+                    i += 2;
+                    continue;
+                }
+                if let Some(grab) = self.find(keycode, state) {
+                    events.push(ShortcutEvent { accelerator: grab.accelerator.clone(), pressed });
+                }
+                i += 1;
+            }
+            events
+        }
+    }
+
+    /// X keysym for a normalized accelerator key name.
+    fn keysym(key: &str) -> Option<u32> {
+        let mut chars = key.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            // Latin-1 keysyms are the code points; letters use the lowercase sym.
+            let c = c.to_ascii_lowercase();
+            return ((c as u32) < 0x100).then_some(c as u32);
+        }
+        if let Some(n) = key.strip_prefix('F').and_then(|n| n.parse::<u32>().ok()) {
+            return Some(0xffbe + n - 1);
+        }
+        Some(match key {
+            "Plus" => 0x2b,
+            "Space" => 0x20,
+            "Tab" => 0xff09,
+            "Enter" => 0xff0d,
+            "Escape" => 0xff1b,
+            "Backspace" => 0xff08,
+            "Delete" => 0xffff,
+            "Insert" => 0xff63,
+            "Home" => 0xff50,
+            "End" => 0xff57,
+            "PageUp" => 0xff55,
+            "PageDown" => 0xff56,
+            "ArrowLeft" => 0xff51,
+            "ArrowUp" => 0xff52,
+            "ArrowRight" => 0xff53,
+            "ArrowDown" => 0xff54,
+            "PrintScreen" => 0xff61,
+            "AudioVolumeDown" => 0x1008ff11,
+            "AudioVolumeMute" => 0x1008ff12,
+            "AudioVolumeUp" => 0x1008ff13,
+            "MediaPlayPause" => 0x1008ff14,
+            "MediaStop" => 0x1008ff15,
+            "MediaTrackPrevious" => 0x1008ff16,
+            "MediaTrackNext" => 0x1008ff17,
+            _ => return None,
+        })
+    }
+}
+
+/// org.freedesktop.portal.GlobalShortcuts. The compositor owns the actual key
+/// binding (and may ask the user to confirm or change it). Each accelerator gets
+/// its own session so it can be dropped independently.
+#[cfg(target_os = "linux")]
+mod portal {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use zbus::zvariant::{OwnedObjectPath, OwnedValue, Value};
+
+    use super::{send_event, Reply};
+    use crate::accelerator::Accelerator;
+
+    const DESKTOP: &str = "org.freedesktop.portal.Desktop";
+    const DESKTOP_PATH: &str = "/org/freedesktop/portal/desktop";
+    const GLOBAL_SHORTCUTS: &str = "org.freedesktop.portal.GlobalShortcuts";
+    const SHORTCUT_ID: &str = "shortcut";
+
+    static NEXT_TOKEN: AtomicU32 = AtomicU32::new(1);
+
+    fn token() -> String {
+        format!("lotus{}", NEXT_TOKEN.fetch_add(1, Ordering::Relaxed))
+    }
+
+    // Session object path -> accelerator as registered.
+    type Sessions = Arc<Mutex<HashMap<String, (Accelerator, String)>>>;
+
+    pub struct Shortcuts {
+        conn: zbus::blocking::Connection,
+        sessions: Sessions,
+    }
+
+    impl Shortcuts {
+        pub fn new() -> Result<Self, String> {
+            let conn = zbus::blocking::Connection::session().map_err(|e| format!("No session bus: {}", e))?;
+            let proxy = zbus::blocking::Proxy::new(&conn, DESKTOP, DESKTOP_PATH, GLOBAL_SHORTCUTS)
+                .map_err(|e| e.to_string())?;
+            let version: u32 = proxy.get_property("version")
+                .map_err(|_| "The desktop has no GlobalShortcuts portal".to_string())?;
+            log::info!("Rust: Using GlobalShortcuts portal v{}", version);
+
+            let sessions: Sessions = Arc::new(Mutex::new(HashMap::new()));
+            for (signal, pressed) in [("Activated", true), ("Deactivated", false)] {
+                let signals = proxy.receive_signal(signal).map_err(|e| e.to_string())?;
+                let sessions = sessions.clone();
+                std::thread::spawn(move || {
+                    for message in signals {
+                        let Ok((session, _id, _timestamp, _options)) = message.body()
+                            .deserialize::<(OwnedObjectPath, String, u64, HashMap<String, OwnedValue>)>() else { continue };
+                        if let Some((_, accelerator)) = sessions.lock().unwrap().get(session.as_str()) {
+                            send_event(accelerator, pressed);
+                        }
+                    }
+                });
+            }
+            Ok(Shortcuts { conn, sessions })
+        }
+
+        pub fn register(&mut self, parsed: Accelerator, accelerator: String, reply: Reply) {
+            if self.sessions.lock().unwrap().values().any(|(a, _)| *a == parsed) {
+                let _ = reply.send(Err(format!("{} is already registered", accelerator)));
+                return;
+            }
+            // The portal may show a confirmation dialog; don't hold up the event loop.
+            let conn = self.conn.clone();
+            let sessions = self.sessions.clone();
+            std::thread::spawn(move || {
+                let result = bind(&conn, &parsed, &accelerator).map(|session| {
+                    sessions.lock().unwrap().insert(session, (parsed, accelerator));
+                });
+                let _ = reply.send(result);
+            });
+        }
+
+        pub fn unregister(&mut self, parsed: &Accelerator) -> bool {
+            let session = {
+                let mut sessions = self.sessions.lock().unwrap();
+                let key = sessions.iter().find(|(_, (a, _))| a == parsed).map(|(k, _)| k.clone());
+                key.and_then(|k| sessions.remove(&k).map(|_| k))
+            };
+            let Some(session) = session else { return false };
+            let _ = self.conn.call_method(Some(DESKTOP), session.as_str(), Some("org.freedesktop.portal.Session"), "Close", &());
+            true
+        }
+    }
+
+    /// Create a session and bind the accelerator in it. Returns the session path.
+    fn bind(conn: &zbus::blocking::Connection, parsed: &Accelerator, accelerator: &str) -> Result<String, String> {
+        let mut options: HashMap<&str, Value> = HashMap::new();
+        options.insert("session_handle_token", Value::from(token()));
+        let results = request(conn, "CreateSession", |handle_token| {
+            options.insert("handle_token", Value::from(handle_token));
+            (options,)
+        })?;
+        let session = results.get("session_handle")
+            .and_then(|v| match &**v {
+                Value::Str(s) => Some(s.to_string()),
+                Value::ObjectPath(p) => Some(p.to_string()),
+                _ => None,
+            })
+            .ok_or("The GlobalShortcuts portal returned no session")?;
+        let session_path = OwnedObjectPath::try_from(session.clone()).map_err(|e| e.to_string())?;
+
+        let mut properties: HashMap<&str, Value> = HashMap::new();
+        properties.insert("description", Value::from(accelerator.to_string()));
+        properties.insert("preferred_trigger", Value::from(trigger(parsed)));
+        let shortcuts = vec![(SHORTCUT_ID, properties)];
+        let results = request(conn, "BindShortcuts", |handle_token| {
+            let mut options: HashMap<&str, Value> = HashMap::new();
+            options.insert("handle_token", Value::from(handle_token));
+            (session_path, shortcuts, "", options)
+        })?;
+
+        // An empty list means the user turned the shortcut down.
+        let bound = results.get("shortcuts")
+            .is_some_and(|v| matches!(&**v, Value::Array(a) if !a.is_empty()));
+        if !bound {
+            let _ = conn.call_method(Some(DESKTOP), session.as_str(), Some("org.freedesktop.portal.Session"), "Close", &());
+            return Err(format!("The compositor did not bind {}", accelerator));
+        }
+        Ok(session)
+    }
+
+    /// Call a portal method and wait for its Response. `body` receives the handle
+    /// token to put in the method's options.
+    fn request<B>(conn: &zbus::blocking::Connection, method: &str, body: impl FnOnce(String) -> B) -> Result<HashMap<String, OwnedValue>, String>
+    where
+        B: serde::Serialize + zbus::zvariant::DynamicType,
+    {
+        let handle_token = token();
+        let sender = conn.unique_name()
+            .map(|n| n.trim_start_matches(':').replace('.', "_"))
+            .ok_or("No unique bus name")?;
+        let request_path = format!("{}/request/{}/{}", DESKTOP_PATH, sender, handle_token);
+        let request = zbus::blocking::Proxy::new(conn, DESKTOP, request_path, "org.freedesktop.portal.Request")
+            .map_err(|e| e.to_string())?;
+        let mut responses = request.receive_signal("Response").map_err(|e| e.to_string())?;
+
+        conn.call_method(Some(DESKTOP), DESKTOP_PATH, Some(GLOBAL_SHORTCUTS), method, &body(handle_token))
+            .map_err(|e| format!("GlobalShortcuts.{} failed: {}", method, e))?;
+        let response = responses.next().ok_or("The GlobalShortcuts portal closed the request")?;
+        let (code, results): (u32, HashMap<String, OwnedValue>) = response.body().deserialize().map_err(|e| e.to_string())?;
+        match code {
+            0 => Ok(results),
+            1 => Err("The shortcut was declined".to_string()),
+            _ => Err(format!("GlobalShortcuts.{} failed", method)),
+        }
+    }
+
+    /// Trigger in the XDG shortcuts format, e.g. "CTRL+SHIFT+a".
+    fn trigger(acc: &Accelerator) -> String {
+        let mut parts = Vec::new();
+        if acc.ctrl { parts.push("CTRL".to_string()); }
+        if acc.alt { parts.push("ALT".to_string()); }
+        if acc.shift { parts.push("SHIFT".to_string()); }
+        if acc.meta { parts.push("LOGO".to_string()); }
+        parts.push(match acc.key.as_str() {
+            "Plus" => "plus".to_string(),
+            "Space" => "space".to_string(),
+            "Enter" => "Return".to_string(),
+            "ArrowUp" => "Up".to_string(),
+            "ArrowDown" => "Down".to_string(),
+            "ArrowLeft" => "Left".to_string(),
+            "ArrowRight" => "Right".to_string(),
+            key if key.chars().count() == 1 => key.to_lowercase(),
+            key => key.to_string(),
+        });
+        parts.join("+")
+    }
+}
@@ -0,0 +1,66 @@
+use std::path::Path;
+
+use winit::window::Icon;
+
+/// Decode PNG bytes into a winit window icon.
+pub fn decode_png(bytes: &[u8]) -> Result<Icon, String> {
+    let (rgba, width, height) = decode_png_rgba(bytes)?;
+    Icon::from_rgba(rgba, width, height).map_err(|e| format!("Invalid icon: {}", e))
+}
+
+/// Decode PNG bytes into straight (non-premultiplied) RGBA8 pixels.
+pub fn decode_png_rgba(bytes: &[u8]) -> Result<(Vec<u8>, u32, u32), String> {
+    let mut decoder = png::Decoder::new(bytes);
+    // Palette -> RGB, sub-byte gray -> 8 bit, 16 bit -> 8 bit, tRNS -> alpha.
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder.read_info().map_err(|e| format!("Invalid PNG: {}", e))?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let frame = reader.next_frame(&mut buf).map_err(|e| format!("Invalid PNG: {}", e))?;
+    let pixels = &buf[..frame.buffer_size()];
+
+    let rgba = match frame.color_type {
+        png::ColorType::Rgba => pixels.to_vec(),
+        png::ColorType::Rgb => pixels.chunks_exact(3).flat_map(|p| [p[0], p[1], p[2], 255]).collect(),
+        png::ColorType::GrayscaleAlpha => pixels.chunks_exact(2).flat_map(|p| [p[0], p[0], p[0], p[1]]).collect(),
+        png::ColorType::Grayscale => pixels.iter().flat_map(|&g| [g, g, g, 255]).collect(),
+        png::ColorType::Indexed => return Err("Unexpanded palette PNG".to_string()),
+    };
+
+    Ok((rgba, frame.width, frame.height))
+}
+
+/// Encode straight RGBA8 pixels as PNG.
+pub fn encode_png_rgba(rgba: &[u8], width: u32, height: u32) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut out, width, height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+        writer.write_image_data(rgba).map_err(|e| e.to_string())?;
+    }
+    Ok(out)
+}
+
+/// Load a PNG icon by path. See `read_image` for how the path is resolved.
+pub fn load_png(path: &str, root: Option<&Path>) -> Result<Icon, String> {
+    decode_png(&read_image(path, root)?)
+}
+
+/// Read image bytes by path. Absolute paths and paths relative to the window's
+/// `root` are read from disk first; anything else is looked up in the app VFS.
+pub fn read_image(path: &str, root: Option<&Path>) -> Result<Vec<u8>, String> {
+    let on_disk = match root {
+        Some(root) if Path::new(path).is_relative() => root.join(path),
+        _ => Path::new(path).to_path_buf(),
+    };
+    if let Ok(bytes) = std::fs::read(&on_disk) {
+        return Ok(bytes);
+    }
+
+    let vfs_path = path.trim_start_matches("./").trim_start_matches('/');
+    crate::APP_STATE.get()
+        .and_then(|state| state.lock().ok())
+        .and_then(|s| s.vfs.as_ref().and_then(|vfs| vfs.read_file(vfs_path)))
+        .ok_or_else(|| format!("Icon not found: {}", path))
+}
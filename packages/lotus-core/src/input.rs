@@ -0,0 +1,90 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use servo::InputEvent;
+use winit::keyboard::PhysicalKey;
+
+/// How long a key may wait for Node's verdict before it goes to the page anyway.
+pub const DEFAULT_BUDGET: Duration = Duration::from_millis(50);
+
+/// A key on its way to a pane.
+pub struct QueuedKey {
+    pub pane_id: String,
+    pub event: InputEvent,
+    pub physical: PhysicalKey,
+    pub pressed: bool,
+    // Ctrl+F6 (Some(backwards)): moves pane focus instead, if there's another pane.
+    pub traversal: Option<bool>,
+}
+
+struct PendingKey {
+    seq: u32,
+    key: QueuedKey,
+    deadline: Instant,
+    // None until Node answers; Some(true) drops the key.
+    prevented: Option<bool>,
+}
+
+/// Keys held back while Node's `before-input` listeners look at them. Keys leave
+/// in the order they arrived, so a slow verdict delays later keys too, but never
+/// by more than the budget.
+pub struct BeforeInput {
+    pub enabled: bool,
+    pub budget: Duration,
+    next_seq: u32,
+    pending: VecDeque<PendingKey>,
+}
+
+impl Default for BeforeInput {
+    fn default() -> Self {
+        BeforeInput { enabled: false, budget: DEFAULT_BUDGET, next_seq: 1, pending: VecDeque::new() }
+    }
+}
+
+impl BeforeInput {
+    /// Queue a key and return the sequence number Node answers with.
+    pub fn hold(&mut self, key: QueuedKey) -> u32 {
+        let seq = self.next_seq;
+        self.next_seq = self.next_seq.wrapping_add(1);
+        self.pending.push_back(PendingKey {
+            seq,
+            key,
+            deadline: Instant::now() + self.budget,
+            prevented: None,
+        });
+        seq
+    }
+
+    pub fn resolve(&mut self, seq: u32, prevent: bool) {
+        if let Some(key) = self.pending.iter_mut().find(|k| k.seq == seq) {
+            key.prevented = Some(prevent);
+        }
+    }
+
+    /// Keys that may go to their pane now, in order. Unanswered keys past their
+    /// deadline are let through; with `all`, everything still queued is.
+    pub fn release(&mut self, all: bool) -> Vec<QueuedKey> {
+        let now = Instant::now();
+        let mut ready = Vec::new();
+        while let Some(front) = self.pending.front() {
+            let prevented = match front.prevented {
+                Some(prevented) => prevented,
+                None if all || now >= front.deadline => false,
+                None => break,
+            };
+            let pending = self.pending.pop_front().unwrap();
+            if !prevented {
+                ready.push(pending.key);
+            }
+        }
+        ready
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.pending.front().map(|k| k.deadline)
+    }
+}
@@ -5,7 +5,7 @@ use std::thread;
 use std::{env, fs, path::PathBuf};
 #[cfg(target_os = "linux")]
 use std::process::Command;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 use std::num::NonZeroUsize;
 
@@ -20,8 +20,25 @@ use log::{info, error, debug, trace, warn};
 
 mod window_state;
 mod platform;
+mod display;
+mod icon;
+mod accelerator;
+#[cfg(target_os = "linux")]
+mod dbusmenu;
+mod menu;
+mod tray;
+mod dialog;
+mod notification;
+mod global_shortcut;
+mod input;
+mod navigation;
+mod popup;
+mod crash;
+mod permission;
+mod auth;
 
 use window_state::WindowStateManager;
+use display::DisplayInfo;
 
 use winit::event::{WindowEvent, MouseScrollDelta};
 use winit::event_loop::{EventLoopProxy, ActiveEventLoop, ControlFlow};
@@ -43,6 +60,8 @@ use servo::{
     WebViewBuilder, WindowRenderingContext, OffscreenRenderingContext, RenderingContext,
     resources::{self, Resource},
     InputEvent, KeyboardEvent as ServoKeyboardEvent,
+    ImeEvent, CompositionEvent, CompositionState, InputMethodType,
+    TouchEvent, TouchEventType, TouchId,
     Code, Key, KeyState, Location, Modifiers,
     MouseButton as ServoMouseButton, MouseButtonAction, MouseButtonEvent, MouseMoveEvent,
     WheelEvent, WheelDelta, WheelMode,
@@ -341,6 +360,16 @@ struct AppState {
     window_start_times: HashMap<String, Instant>,
     vfs: Option<Arc<EncryptedVfs>>,
     resource_cache: ByteLimitedLruCache,
+    // Last monitor snapshot taken on the event loop thread; served to get_displays().
+    displays: Vec<DisplayInfo>,
+    // App identifier (also the state directory name); shown as the notification sender.
+    app_id: String,
+    zoom_levels: window_state::ZoomLevels,
+    permissions: window_state::PermissionDecisions,
+    credentials: auth::CredentialCache,
+    // Secret per pane -> (window_id, pane_id). Identifies the pane a Servo
+    // error page reports from, since the IPC client id is page-controlled.
+    pane_keys: HashMap<String, (String, String)>,
 }
 
 // IPC bootstrap script injected into every page
@@ -513,7 +542,7 @@ window.lotus = {
         const dataLen = data ? (typeof data === 'string' ? data.length * 2 : (data.byteLength || 0)) : 0;
         window.lotus._batchBytes += dataLen;
 
-        if (channel === 'lotus:set-drag-regions') {
+        if (channel === 'lotus:set-drag-regions' || channel === 'lotus:load-failed') {
             window.lotus._batchHasControl = true;
         }
 
@@ -725,6 +754,87 @@ const DRAG_REGION_SCRIPT: &str = r#"
 })();
 "#;
 
+// Servo doesn't pass window.open's target and features (or a link's target)
+// to the embedder. Record them as hints for new-window-requested; the popup's
+// delegate reads them back with POPUP_OPEN_SCRIPT. The URL itself comes from
+// the popup's navigation request, since the page can rewrite anything here.
+const WINDOW_OPEN_SCRIPT: &str = r#"
+(function() {
+    if (!window.lotus) return;
+    const calls = window.lotus._openCalls = [];
+    const record = (url, target, features) => {
+        const now = Date.now();
+        // Drop link clicks that never produced a popup (e.g. a named target that already existed).
+        while (calls.length && now - calls[0].time > 1000) calls.shift();
+        const href = url === undefined || url === null ? '' : String(url);
+        const call = { navigates: href !== '' && href !== 'about:blank', target: target ? String(target) : '', features: features ? String(features) : '', time: now };
+        calls.push(call);
+        return call;
+    };
+    const opened = new WeakSet();
+    const open = window.open;
+    window.open = function(url, target, features) {
+        const call = record(url, target, features);
+        const popup = open.apply(this, arguments);
+        // Blocked, or reused a window this page already opened: no popup reads this call.
+        if (!popup || opened.has(popup)) {
+            const i = calls.indexOf(call);
+            if (i !== -1) calls.splice(i, 1);
+        } else {
+            opened.add(popup);
+        }
+        return popup;
+    };
+    window.addEventListener('click', (e) => {
+        if (e.defaultPrevented) return;
+        const link = e.target && e.target.closest ? e.target.closest('a[target]') : null;
+        const target = link ? link.getAttribute('target') : '';
+        if (link && target && !['_self', '_parent', '_top'].includes(target.toLowerCase())) {
+            record(link.href, target, '');
+        }
+    });
+})();
+"#;
+
+const POPUP_OPEN_SCRIPT: &str = "JSON.stringify((window.lotus && window.lotus._openCalls && window.lotus._openCalls.shift()) || null)";
+
+// Servo hands page notifications to the embedder but has no way to route a
+// click back. Keep the page's live Notifications so the event loop can fire
+// their events through window.lotus._notificationEvent(title, tag, type).
+const NOTIFICATION_SCRIPT: &str = r#"
+(function() {
+    const Native = window.Notification;
+    if (!Native || !window.lotus) return;
+    const live = [];
+    const forget = (n) => {
+        const i = live.indexOf(n);
+        if (i !== -1) live.splice(i, 1);
+    };
+    window.Notification = class Notification extends Native {
+        constructor(title, options) {
+            super(title, options);
+            live.push(this);
+            const close = this.close;
+            this.close = function() {
+                forget(this);
+                return close.apply(this, arguments);
+            };
+        }
+    };
+    window.lotus._notificationEvent = (title, tag, type) => {
+        // Newest first: a reused tag replaces the older notification.
+        for (let i = live.length - 1; i >= 0; i--) {
+            const n = live[i];
+            if (n.title === title && n.tag === tag) {
+                if (type !== 'click') live.splice(i, 1);
+                n.dispatchEvent(new Event(type));
+                return;
+            }
+        }
+    };
+})();
+"#;
+
 const STENCIL_VS: &str = r#"
 #version 330 core
 const vec2 verts[4] = vec2[4](vec2(-1.0, -1.0), vec2(1.0, -1.0), vec2(-1.0, 1.0), vec2(1.0, 1.0));
@@ -773,6 +883,7 @@ out vec4 f_color;
 uniform sampler2D u_texture;
 uniform vec2 u_size;
 uniform float u_radius;
+uniform float u_opacity;
 
 float sdRoundedBox(vec2 p, vec2 b, float r) {
     vec2 q = abs(p) - b + r;
@@ -790,15 +901,56 @@ void main() {
     
     vec4 tex_color = texture(u_texture, v_uv);
     // Premultiplied alpha blending: multiply texture alpha by the mask
-    f_color = tex_color * alpha_mask;
+    f_color = tex_color * alpha_mask * u_opacity;
 }
 "#;
 
 struct WindowMetadata {
     root_path: Option<PathBuf>,
     last_window_size: Option<winit::dpi::PhysicalSize<u32>>,
+    persist_zoom: bool,
+    // Navigation allowlists: the window's, and per-pane overrides.
+    allowed_origins: Option<Vec<String>>,
+    pane_origins: HashMap<String, Vec<String>>,
+    // Crash recovery: the window's policy, per-pane overrides, and when each pane was last reloaded for a crash.
+    crash_policy: crash::CrashPolicy,
+    pane_crash_policies: HashMap<String, crash::CrashPolicy>,
+    crash_reloads: HashMap<String, Instant>,
+    // Error page template, as a path under the window's root.
+    error_page: Option<String>,
+}
+
+impl WindowMetadata {
+    fn allowed_origins(&self, pane_id: &str) -> Option<&[String]> {
+        self.pane_origins.get(pane_id).or(self.allowed_origins.as_ref()).map(|v| v.as_slice())
+    }
+
+    fn crash_policy(&self, pane_id: &str) -> crash::CrashPolicy {
+        self.pane_crash_policies.get(pane_id).copied().unwrap_or(self.crash_policy)
+    }
+}
+
+/// Parse a crash policy from window or pane options; bad values keep the default.
+fn parse_crash_policy(policy: Option<&String>) -> Option<crash::CrashPolicy> {
+    let policy = policy?;
+    crash::CrashPolicy::parse(policy).map_err(|e| warn!("Rust: {}", e)).ok()
+}
+
+/// Key for per-origin zoom and permissions: scheme, host and port of a URL. Works for
+/// lotus-resource:// and file:// URLs too, which have opaque web origins.
+fn url_origin(url: &str) -> String {
+    match url.split_once("://") {
+        Some((scheme, rest)) => {
+            let authority = rest.split(['/', '?', '#']).next().unwrap_or("");
+            format!("{}://{}", scheme, authority)
+        }
+        None => url.to_string(),
+    }
 }
 
+const MIN_ZOOM: f32 = 0.25;
+const MAX_ZOOM: f32 = 5.0;
+
 fn map_winit_modifiers(winit_mods: winit::event::Modifiers) -> Modifiers {
     let mut s_mods = Modifiers::empty();
     let state = winit_mods.state();
@@ -847,8 +999,93 @@ fn map_winit_key(winit_key: &winit::keyboard::Key) -> Key {
             WNamed::Control => Key::Named(SKey::Control),
             WNamed::Alt => Key::Named(SKey::Alt),
             WNamed::Super => Key::Named(SKey::Meta),
+            WNamed::AltGraph => Key::Named(SKey::AltGraph),
+            WNamed::CapsLock => Key::Named(SKey::CapsLock),
+            WNamed::Fn => Key::Named(SKey::Fn),
+            WNamed::FnLock => Key::Named(SKey::FnLock),
+            WNamed::NumLock => Key::Named(SKey::NumLock),
+            WNamed::ScrollLock => Key::Named(SKey::ScrollLock),
+            WNamed::Symbol => Key::Named(SKey::Symbol),
+            WNamed::SymbolLock => Key::Named(SKey::SymbolLock),
+            WNamed::Meta => Key::Named(SKey::Meta),
+            WNamed::Hyper => Key::Named(SKey::Hyper),
+            WNamed::Clear => Key::Named(SKey::Clear),
+            WNamed::Copy => Key::Named(SKey::Copy),
+            WNamed::CrSel => Key::Named(SKey::CrSel),
+            WNamed::Cut => Key::Named(SKey::Cut),
+            WNamed::EraseEof => Key::Named(SKey::EraseEof),
+            WNamed::ExSel => Key::Named(SKey::ExSel),
+            WNamed::Paste => Key::Named(SKey::Paste),
+            WNamed::Redo => Key::Named(SKey::Redo),
+            WNamed::Undo => Key::Named(SKey::Undo),
+            WNamed::Accept => Key::Named(SKey::Accept),
+            WNamed::Again => Key::Named(SKey::Again),
+            WNamed::Attn => Key::Named(SKey::Attn),
+            WNamed::Cancel => Key::Named(SKey::Cancel),
+            WNamed::ContextMenu => Key::Named(SKey::ContextMenu),
+            WNamed::Execute => Key::Named(SKey::Execute),
+            WNamed::Find => Key::Named(SKey::Find),
+            WNamed::Help => Key::Named(SKey::Help),
+            WNamed::Pause => Key::Named(SKey::Pause),
+            WNamed::Play => Key::Named(SKey::Play),
+            WNamed::Props => Key::Named(SKey::Props),
+            WNamed::Select => Key::Named(SKey::Select),
+            WNamed::ZoomIn => Key::Named(SKey::ZoomIn),
+            WNamed::ZoomOut => Key::Named(SKey::ZoomOut),
+            WNamed::BrightnessDown => Key::Named(SKey::BrightnessDown),
+            WNamed::BrightnessUp => Key::Named(SKey::BrightnessUp),
+            WNamed::Eject => Key::Named(SKey::Eject),
+            WNamed::LogOff => Key::Named(SKey::LogOff),
+            WNamed::Power => Key::Named(SKey::Power),
+            WNamed::PowerOff => Key::Named(SKey::PowerOff),
+            WNamed::PrintScreen => Key::Named(SKey::PrintScreen),
+            WNamed::Hibernate => Key::Named(SKey::Hibernate),
+            WNamed::Standby => Key::Named(SKey::Standby),
+            WNamed::WakeUp => Key::Named(SKey::WakeUp),
+            WNamed::Compose => Key::Named(SKey::Compose),
+            WNamed::Convert => Key::Named(SKey::Convert),
+            WNamed::NonConvert => Key::Named(SKey::NonConvert),
+            WNamed::ModeChange => Key::Named(SKey::ModeChange),
+            WNamed::Process => Key::Named(SKey::Process),
+            WNamed::KanaMode => Key::Named(SKey::KanaMode),
+            WNamed::KanjiMode => Key::Named(SKey::KanjiMode),
+            WNamed::HangulMode => Key::Named(SKey::HangulMode),
+            WNamed::HanjaMode => Key::Named(SKey::HanjaMode),
+            WNamed::Hiragana => Key::Named(SKey::Hiragana),
+            WNamed::Katakana => Key::Named(SKey::Katakana),
+            WNamed::HiraganaKatakana => Key::Named(SKey::HiraganaKatakana),
+            WNamed::Zenkaku => Key::Named(SKey::Zenkaku),
+            WNamed::Hankaku => Key::Named(SKey::Hankaku),
+            WNamed::ZenkakuHankaku => Key::Named(SKey::ZenkakuHankaku),
+            WNamed::Eisu => Key::Named(SKey::Eisu),
+            WNamed::Romaji => Key::Named(SKey::Romaji),
+            WNamed::MediaPlayPause => Key::Named(SKey::MediaPlayPause),
+            WNamed::MediaPlay => Key::Named(SKey::MediaPlay),
+            WNamed::MediaPause => Key::Named(SKey::MediaPause),
+            WNamed::MediaStop => Key::Named(SKey::MediaStop),
+            WNamed::MediaTrackNext => Key::Named(SKey::MediaTrackNext),
+            WNamed::MediaTrackPrevious => Key::Named(SKey::MediaTrackPrevious),
+            WNamed::MediaFastForward => Key::Named(SKey::MediaFastForward),
+            WNamed::MediaRewind => Key::Named(SKey::MediaRewind),
+            WNamed::MediaRecord => Key::Named(SKey::MediaRecord),
+            WNamed::AudioVolumeDown => Key::Named(SKey::AudioVolumeDown),
+            WNamed::AudioVolumeUp => Key::Named(SKey::AudioVolumeUp),
+            WNamed::AudioVolumeMute => Key::Named(SKey::AudioVolumeMute),
+            WNamed::BrowserBack => Key::Named(SKey::BrowserBack),
+            WNamed::BrowserForward => Key::Named(SKey::BrowserForward),
+            WNamed::BrowserRefresh => Key::Named(SKey::BrowserRefresh),
+            WNamed::BrowserHome => Key::Named(SKey::BrowserHome),
+            WNamed::BrowserSearch => Key::Named(SKey::BrowserSearch),
+            WNamed::BrowserFavorites => Key::Named(SKey::BrowserFavorites),
+            WNamed::BrowserStop => Key::Named(SKey::BrowserStop),
+            WNamed::Close => Key::Named(SKey::Close),
+            WNamed::New => Key::Named(SKey::New),
+            WNamed::Open => Key::Named(SKey::Open),
+            WNamed::Print => Key::Named(SKey::Print),
+            WNamed::Save => Key::Named(SKey::Save),
             _ => Key::Named(SKey::Unidentified),
         },
+        WKey::Dead(_) => Key::Named(SKey::Dead),
         _ => Key::Named(SKey::Unidentified),
     }
 }
@@ -911,12 +1148,113 @@ fn map_winit_code(winit_code: winit::keyboard::PhysicalKey) -> Code {
             WKeyCode::AltRight => Code::AltRight,
             WKeyCode::SuperLeft => Code::MetaLeft,
             WKeyCode::SuperRight => Code::MetaRight,
+            WKeyCode::Backquote => Code::Backquote,
+            WKeyCode::Backslash => Code::Backslash,
+            WKeyCode::BracketLeft => Code::BracketLeft,
+            WKeyCode::BracketRight => Code::BracketRight,
+            WKeyCode::Comma => Code::Comma,
+            WKeyCode::Equal => Code::Equal,
+            WKeyCode::IntlBackslash => Code::IntlBackslash,
+            WKeyCode::IntlRo => Code::IntlRo,
+            WKeyCode::IntlYen => Code::IntlYen,
+            WKeyCode::Minus => Code::Minus,
+            WKeyCode::Period => Code::Period,
+            WKeyCode::Quote => Code::Quote,
+            WKeyCode::Semicolon => Code::Semicolon,
+            WKeyCode::Slash => Code::Slash,
+            WKeyCode::CapsLock => Code::CapsLock,
+            WKeyCode::ContextMenu => Code::ContextMenu,
+            WKeyCode::Delete => Code::Delete,
+            WKeyCode::End => Code::End,
+            WKeyCode::Help => Code::Help,
+            WKeyCode::Home => Code::Home,
+            WKeyCode::Insert => Code::Insert,
+            WKeyCode::PageDown => Code::PageDown,
+            WKeyCode::PageUp => Code::PageUp,
+            WKeyCode::NumLock => Code::NumLock,
+            WKeyCode::ScrollLock => Code::ScrollLock,
+            WKeyCode::PrintScreen => Code::PrintScreen,
+            WKeyCode::Pause => Code::Pause,
+            WKeyCode::Numpad0 => Code::Numpad0,
+            WKeyCode::Numpad1 => Code::Numpad1,
+            WKeyCode::Numpad2 => Code::Numpad2,
+            WKeyCode::Numpad3 => Code::Numpad3,
+            WKeyCode::Numpad4 => Code::Numpad4,
+            WKeyCode::Numpad5 => Code::Numpad5,
+            WKeyCode::Numpad6 => Code::Numpad6,
+            WKeyCode::Numpad7 => Code::Numpad7,
+            WKeyCode::Numpad8 => Code::Numpad8,
+            WKeyCode::Numpad9 => Code::Numpad9,
+            WKeyCode::NumpadAdd => Code::NumpadAdd,
+            WKeyCode::NumpadComma => Code::NumpadComma,
+            WKeyCode::NumpadDecimal => Code::NumpadDecimal,
+            WKeyCode::NumpadDivide => Code::NumpadDivide,
+            WKeyCode::NumpadEnter => Code::NumpadEnter,
+            WKeyCode::NumpadEqual => Code::NumpadEqual,
+            WKeyCode::NumpadMultiply => Code::NumpadMultiply,
+            WKeyCode::NumpadSubtract => Code::NumpadSubtract,
+            WKeyCode::F1 => Code::F1,
+            WKeyCode::F2 => Code::F2,
+            WKeyCode::F3 => Code::F3,
+            WKeyCode::F4 => Code::F4,
+            WKeyCode::F5 => Code::F5,
+            WKeyCode::F6 => Code::F6,
+            WKeyCode::F7 => Code::F7,
+            WKeyCode::F8 => Code::F8,
+            WKeyCode::F9 => Code::F9,
+            WKeyCode::F10 => Code::F10,
+            WKeyCode::F11 => Code::F11,
+            WKeyCode::F12 => Code::F12,
+            WKeyCode::Convert => Code::Convert,
+            WKeyCode::NonConvert => Code::NonConvert,
+            WKeyCode::KanaMode => Code::KanaMode,
+            WKeyCode::Lang1 => Code::Lang1,
+            WKeyCode::Lang2 => Code::Lang2,
+            WKeyCode::Fn => Code::Fn,
+            WKeyCode::AudioVolumeDown => Code::AudioVolumeDown,
+            WKeyCode::AudioVolumeUp => Code::AudioVolumeUp,
+            WKeyCode::AudioVolumeMute => Code::AudioVolumeMute,
+            WKeyCode::MediaPlayPause => Code::MediaPlayPause,
+            WKeyCode::MediaStop => Code::MediaStop,
+            WKeyCode::MediaTrackNext => Code::MediaTrackNext,
+            WKeyCode::MediaTrackPrevious => Code::MediaTrackPrevious,
+            WKeyCode::MediaSelect => Code::MediaSelect,
+            WKeyCode::BrowserBack => Code::BrowserBack,
+            WKeyCode::BrowserForward => Code::BrowserForward,
+            WKeyCode::BrowserRefresh => Code::BrowserRefresh,
+            WKeyCode::BrowserHome => Code::BrowserHome,
+            WKeyCode::BrowserSearch => Code::BrowserSearch,
+            WKeyCode::BrowserFavorites => Code::BrowserFavorites,
+            WKeyCode::BrowserStop => Code::BrowserStop,
+            WKeyCode::Power => Code::Power,
+            WKeyCode::Sleep => Code::Sleep,
+            WKeyCode::WakeUp => Code::WakeUp,
+            WKeyCode::Eject => Code::Eject,
+            WKeyCode::Again => Code::Again,
+            WKeyCode::Copy => Code::Copy,
+            WKeyCode::Cut => Code::Cut,
+            WKeyCode::Paste => Code::Paste,
+            WKeyCode::Undo => Code::Undo,
+            WKeyCode::Find => Code::Find,
+            WKeyCode::Open => Code::Open,
+            WKeyCode::Props => Code::Props,
+            WKeyCode::Select => Code::Select,
             _ => Code::Unidentified,
         },
         _ => Code::Unidentified,
     }
 }
 
+fn map_winit_location(location: winit::keyboard::KeyLocation) -> Location {
+    use winit::keyboard::KeyLocation as WLocation;
+    match location {
+        WLocation::Standard => Location::Standard,
+        WLocation::Left => Location::Left,
+        WLocation::Right => Location::Right,
+        WLocation::Numpad => Location::Numpad,
+    }
+}
+
 use glow::HasContext;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -942,6 +1280,62 @@ impl From<u32> for PaneAnchor {
     }
 }
 
+/// A close held back while a native dialog still holds the window's handles,
+/// by where it came from: the window manager or `win.close()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeferredClose {
+    Requested,
+    Command,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FullscreenMode {
+    None = 0,
+    Borderless = 1,
+    Exclusive = 2,
+}
+
+impl From<u32> for FullscreenMode {
+    fn from(v: u32) -> Self {
+        match v {
+            1 => FullscreenMode::Borderless,
+            2 => FullscreenMode::Exclusive,
+            _ => FullscreenMode::None,
+        }
+    }
+}
+
+impl FullscreenMode {
+    fn as_str(&self) -> &'static str {
+        match self {
+            FullscreenMode::None => "none",
+            FullscreenMode::Borderless => "borderless",
+            FullscreenMode::Exclusive => "exclusive",
+        }
+    }
+
+    /// Resolve the mode into a winit fullscreen request on the window's current monitor.
+    /// Exclusive picks the largest, fastest video mode and falls back to borderless
+    /// when the platform reports none (Wayland never exposes video modes).
+    fn to_winit(&self, window: &Window) -> Option<winit::window::Fullscreen> {
+        match self {
+            FullscreenMode::None => None,
+            FullscreenMode::Borderless => Some(winit::window::Fullscreen::Borderless(window.current_monitor())),
+            FullscreenMode::Exclusive => {
+                let video_mode = window.current_monitor().and_then(|m| {
+                    m.video_modes().max_by_key(|v| {
+                        (v.size().width as u64 * v.size().height as u64, v.refresh_rate_millihertz())
+                    })
+                });
+                match video_mode {
+                    Some(v) => Some(winit::window::Fullscreen::Exclusive(v)),
+                    None => Some(winit::window::Fullscreen::Borderless(window.current_monitor())),
+                }
+            }
+        }
+    }
+}
+
 struct PaneInstance {
     id: String,
     webview: servo::WebView,
@@ -982,6 +1376,8 @@ struct PaneInstance {
     pub frames_until_stable: u8,
     pub ghost_tex: Option<glow::NativeTexture>,
     pub ghost_tex_size: Option<winit::dpi::PhysicalSize<u32>>,
+    // Session history as last reported by Servo: entry URLs and the current index.
+    pub history: (Vec<String>, usize),
 }
 
 #[derive(Debug, Clone)]
@@ -1031,6 +1427,7 @@ struct WindowInstance {
     pub comp_vbo: Option<glow::NativeBuffer>,
     pub u_comp_size_loc: Option<glow::UniformLocation>,
     pub u_comp_radius_loc: Option<glow::UniformLocation>,
+    pub u_comp_opacity_loc: Option<glow::UniformLocation>,
     pub comp_tex_size: Option<winit::dpi::PhysicalSize<u32>>,
     pub scene_fbo: Option<glow::NativeFramebuffer>,
     pub scene_tex: Option<glow::NativeTexture>,
@@ -1038,9 +1435,143 @@ struct WindowInstance {
     pub committed_layout: Option<StagedLayout>,
     pub active_window_size: winit::dpi::PhysicalSize<u32>,
     pub committed_window_size: Option<winit::dpi::PhysicalSize<u32>>,
+    pub fullscreen_mode: FullscreenMode,
+    // Outer position + inner size captured before entering fullscreen, so window
+    // state persistence records the windowed bounds rather than the monitor size.
+    pub windowed_bounds: Option<(winit::dpi::PhysicalPosition<i32>, winit::dpi::PhysicalSize<u32>)>,
+    pub parent_id: Option<String>,
+    pub modal: bool,
+    // Compositor-side opacity (1.0 when the window manager applies it instead).
+    pub opacity: f32,
+    // Click-through: the OS stops delivering pointer input. With forward_hover,
+    // about_to_wait polls the global cursor and feeds CursorMoved to the panes.
+    pub ignore_mouse_events: bool,
+    pub forward_hover: bool,
+    pub last_forwarded_cursor: Option<winit::dpi::PhysicalPosition<f64>>,
+    pub menu_bar: Option<menu::MenuBar>,
+    // Native dialogs currently parented to this window; input is blocked while > 0.
+    pub open_dialogs: u32,
+    // A close that came in while a dialog was open, carried out when the last one ends.
+    pub deferred_close: Option<DeferredClose>,
+    // winit's view of the modifiers, for matching menu accelerators.
+    winit_modifiers: winit::keyboard::ModifiersState,
+    // Keys whose press Lotus handled itself; their release doesn't reach the page either.
+    consumed_keys: HashSet<winit::keyboard::PhysicalKey>,
+    // Accelerators Node registered on this window -> the string it used.
+    pub accelerators: HashMap<accelerator::Accelerator, String>,
+    // Keys waiting on Node's `before-input` verdict.
+    pub before_input: input::BeforeInput,
+    // An IME composition is in progress in the active pane.
+    ime_composing: bool,
+    // The preedit was cleared. IMEs do that before a commit and when the user
+    // cancels; if a new preedit comes instead of the commit, the old
+    // composition is ended first.
+    ime_preedit_cleared: bool,
+    // Pane each active touch point started in; moves and lifts stay with it.
+    touch_panes: HashMap<u64, String>,
+    // Pane ids in keyboard traversal order, set from Node. Empty = by position.
+    pub focus_order: Vec<String>,
+    // Navigations outside the allowlist, waiting on Node's `will-navigate` answer.
+    pending_navigations: HashMap<u32, (String, navigation::PendingNavigation)>,
+    next_navigation_seq: u32,
 }
 
 impl WindowInstance {
+    /// Topmost visible pane under a window point, hit-tested like the mouse.
+    fn pane_at(&self, point: Point2D<f32, servo::DevicePixel>) -> Option<&PaneInstance> {
+        let scale_factor = self.window.scale_factor() as f32;
+        let mut sorted_panes: Vec<_> = self.panes.values().filter(|p| p.is_visible).collect();
+        sorted_panes.sort_by_key(|p| -p.z_index);
+        sorted_panes.into_iter().find(|p| p.physical_rect(scale_factor).contains(point))
+    }
+
+    /// Close the active pane's open composition, if any, so the page isn't
+    /// left waiting for a compositionend.
+    fn dismiss_ime(&mut self) {
+        self.ime_preedit_cleared = false;
+        if std::mem::take(&mut self.ime_composing) {
+            if let Some(pane) = self.panes.get(&self.active_pane_id) {
+                pane.webview.notify_input_event(InputEvent::Ime(ImeEvent::Dismissed));
+            }
+        }
+    }
+
+    /// Move keyboard focus to another pane. Any IME session belonged to the old
+    /// one; the new pane asks for it again if an editable element has focus.
+    /// Returns the pane that lost focus, if focus changed.
+    fn focus_pane(&mut self, pane_id: String) -> Option<String> {
+        if self.active_pane_id == pane_id || !self.panes.contains_key(&pane_id) {
+            return None;
+        }
+        self.dismiss_ime();
+        let previous = std::mem::replace(&mut self.active_pane_id, pane_id);
+        self.window.set_ime_allowed(false);
+        if let Some(pane) = self.panes.get(&previous) {
+            pane.webview.blur();
+        }
+        if let Some(pane) = self.panes.get(&self.active_pane_id) {
+            pane.webview.focus();
+        }
+        Some(previous)
+    }
+
+    /// The pane Ctrl+F6 (or Ctrl+Shift+F6 with `backwards`) moves to: the order
+    /// set with `set_focus_order`, then remaining visible panes top to bottom,
+    /// left to right.
+    fn next_focus_pane(&self, backwards: bool) -> Option<String> {
+        let scale_factor = self.window.scale_factor() as f32;
+        let mut rest: Vec<&PaneInstance> = self.panes.values()
+            .filter(|p| p.is_visible && !self.focus_order.contains(&p.id))
+            .collect();
+        rest.sort_by(|a, b| {
+            let (a, b) = (a.physical_rect(scale_factor).origin, b.physical_rect(scale_factor).origin);
+            a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x))
+        });
+        let order: Vec<&String> = self.focus_order.iter()
+            .filter(|id| self.panes.get(*id).is_some_and(|p| p.is_visible))
+            .chain(rest.into_iter().map(|p| &p.id))
+            .collect();
+        if order.is_empty() {
+            return None;
+        }
+        let current = order.iter().position(|id| **id == self.active_pane_id);
+        let next = match (current, backwards) {
+            (Some(i), false) => (i + 1) % order.len(),
+            (Some(i), true) => (i + order.len() - 1) % order.len(),
+            (None, false) => 0,
+            (None, true) => order.len() - 1,
+        };
+        Some(order[next].clone())
+    }
+
+    /// Deliver keys that `before-input` has finished with to their panes.
+    /// Deliver the keys before-input has let through. Returns the focus
+    /// changes Ctrl+F6 made on the way, as (blurred, focused).
+    fn release_input(&mut self, all: bool) -> Vec<(Option<String>, String)> {
+        self.before_input.release(all).into_iter().filter_map(|key| self.deliver_key(key)).collect()
+    }
+
+    /// Hand a key to its pane. Ctrl+F6 moves focus instead when there's
+    /// another pane to move to (its release is swallowed too); otherwise the
+    /// page gets it like any key.
+    fn deliver_key(&mut self, key: input::QueuedKey) -> Option<(Option<String>, String)> {
+        if !key.pressed && self.consumed_keys.remove(&key.physical) {
+            return None;
+        }
+        if let Some(backwards) = key.traversal {
+            let next = self.next_focus_pane(backwards).filter(|id| *id != self.active_pane_id);
+            if let Some(pane_id) = next {
+                self.consumed_keys.insert(key.physical);
+                let blurred = self.focus_pane(pane_id.clone());
+                return Some((blurred, pane_id));
+            }
+        }
+        if let Some(pane) = self.panes.get(&key.pane_id) {
+            pane.webview.notify_input_event(key.event);
+        }
+        None
+    }
+
     pub fn init_stencil_program(&mut self) {
         use glow::HasContext;
         unsafe {
@@ -1114,6 +1645,7 @@ impl WindowInstance {
             
             self.u_comp_size_loc = self.gl.get_uniform_location(program, "u_size");
             self.u_comp_radius_loc = self.gl.get_uniform_location(program, "u_radius");
+            self.u_comp_opacity_loc = self.gl.get_uniform_location(program, "u_opacity");
             self.comp_program = Some(program);
 
             let vao = self.gl.create_vertex_array().expect("Cannot create VAO");
@@ -1313,6 +1845,86 @@ impl WindowInstance {
             }
         }
     }
+
+    /// Apply a fullscreen mode to the native window. Returns false if the window
+    /// was already in the requested mode so callers can skip emitting events.
+    pub fn set_fullscreen_mode(&mut self, mode: FullscreenMode) -> bool {
+        if self.fullscreen_mode == mode {
+            return false;
+        }
+
+        if self.fullscreen_mode == FullscreenMode::None {
+            if let Ok(pos) = self.window.outer_position() {
+                self.windowed_bounds = Some((pos, self.window.inner_size()));
+            }
+        }
+
+        self.window.set_fullscreen(mode.to_winit(&self.window));
+        self.fullscreen_mode = mode;
+
+        if mode == FullscreenMode::None {
+            self.windowed_bounds = None;
+            // Keep the page-side Fullscreen API in sync when Node (or the user)
+            // leaves fullscreen while an element is still fullscreened.
+            for pane in self.panes.values() {
+                pane.webview.exit_fullscreen();
+            }
+        }
+        true
+    }
+
+    /// Point-in-time view of the window for the awaitable napi getters.
+    pub fn snapshot(&self) -> WindowSnapshot {
+        let sf = self.window.scale_factor();
+        let position = self.window.outer_position().unwrap_or_default();
+        let size = self.window.inner_size();
+
+        let mut panes: Vec<PaneInfo> = self.panes.values().map(|pane| {
+            // Anchored panes are laid out by recalculate_layout, so their
+            // authoritative rect is the physical one.
+            let (x, y, width, height) = if pane.anchor != PaneAnchor::None {
+                let r = pane.last_physical_rect;
+                (r.origin.x as f64 / sf, r.origin.y as f64 / sf, r.size.width as f64 / sf, r.size.height as f64 / sf)
+            } else {
+                (pane.rect.origin.x as f64, pane.rect.origin.y as f64, pane.rect.size.width as f64, pane.rect.size.height as f64)
+            };
+            PaneInfo {
+                id: pane.id.clone(),
+                url: pane.webview.url().map(|u| u.to_string()),
+                title: pane.webview.page_title(),
+                x,
+                y,
+                width,
+                height,
+                z_index: pane.z_index,
+                visible: pane.is_visible,
+                anchor: pane.anchor as u32,
+                dock_order: pane.dock_order,
+                focused: pane.id == self.active_pane_id,
+                zoom: pane.webview.page_zoom() as f64,
+                can_go_back: pane.history.1 > 0,
+                can_go_forward: pane.history.1 + 1 < pane.history.0.len(),
+            }
+        }).collect();
+        panes.sort_by_key(|p| p.z_index);
+
+        WindowSnapshot {
+            bounds: WindowBounds {
+                x: position.x,
+                y: position.y,
+                width: size.width,
+                height: size.height,
+            },
+            maximized: self.window.is_maximized(),
+            minimized: self.window.is_minimized().unwrap_or(false),
+            visible: self.window.is_visible().unwrap_or(true),
+            focused: self.window.has_focus(),
+            fullscreen: self.fullscreen_mode != FullscreenMode::None,
+            scale_factor: sf,
+            title: self.window.title(),
+            panes,
+        }
+    }
 }
 
 
@@ -1338,6 +1950,58 @@ pub struct WindowOptions {
     pub panes: Vec<PaneOptions>,
     pub id: Option<String>,
     pub wm_class: Option<String>,
+    pub display: Option<String>, // display id to open on (see App::get_displays)
+    pub center: Option<bool>,    // center in the target (or primary) display's work area
+    pub parent: Option<String>,  // owner window id: child stays above it and is centered on it
+    pub modal: Option<bool>,     // block input to the owner while this window is open
+    pub icon: Option<String>,    // PNG path (absolute, relative to root, or in the VFS)
+    pub persist_zoom: Option<bool>, // remember pane zoom per origin across runs
+    pub allowed_origins: Option<Vec<String>>, // navigation allowlist for every pane; None = anywhere
+    pub crash_policy: Option<String>, // "keep" | "reload" | "show-error-page"
+    pub error_page: Option<String>, // lotus-resource template shown when a page fails to load
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, Serialize)]
+pub struct WindowBounds {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, Serialize)]
+pub struct PaneInfo {
+    pub id: String,
+    pub url: Option<String>,
+    pub title: Option<String>,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub z_index: i32,
+    pub visible: bool,
+    pub anchor: u32,
+    pub dock_order: u32,
+    pub focused: bool,
+    pub zoom: f64,
+    pub can_go_back: bool,
+    pub can_go_forward: bool,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, Serialize)]
+pub struct WindowSnapshot {
+    pub bounds: WindowBounds, // outer position, inner size (physical pixels)
+    pub maximized: bool,
+    pub minimized: bool,
+    pub visible: bool,
+    pub focused: bool,
+    pub fullscreen: bool,
+    pub scale_factor: f64,
+    pub title: String,
+    pub panes: Vec<PaneInfo>,
 }
 
 #[napi(object)]
@@ -1353,6 +2017,8 @@ pub struct PaneOptions {
     pub visible: bool,
     pub anchor: Option<u32>,
     pub dock_order: Option<u32>,
+    pub allowed_origins: Option<Vec<String>>, // overrides the window's allowlist for this pane
+    pub crash_policy: Option<String>, // overrides the window's crash policy for this pane
 }
 
 impl Default for WindowOptions {
@@ -1376,11 +2042,50 @@ impl Default for WindowOptions {
             panes: Vec::new(),
             id: None,
             wm_class: None,
+            display: None,
+            center: None,
+            parent: None,
+            modal: None,
+            icon: None,
+            persist_zoom: None,
+            allowed_origins: None,
+            crash_policy: None,
+            error_page: None,
         }
     }
 }
 
 impl PaneInstance {
+    /// The pane's area within the window, in physical pixels.
+    fn physical_rect(&self, scale_factor: f32) -> euclid::Rect<f32, servo::DevicePixel> {
+        let (px, py, pw, ph) = if self.anchor != PaneAnchor::None {
+            (
+                self.last_physical_rect.origin.x as f32,
+                self.last_physical_rect.origin.y as f32,
+                self.last_physical_rect.size.width as f32,
+                self.last_physical_rect.size.height as f32
+            )
+        } else {
+            let physical_rect = self.rect.scale(scale_factor, scale_factor);
+            (
+                physical_rect.origin.x.round(),
+                physical_rect.origin.y.round(),
+                physical_rect.size.width.round(),
+                physical_rect.size.height.round()
+            )
+        };
+        euclid::Rect::new(euclid::Point2D::new(px, py), euclid::Size2D::new(pw, ph))
+    }
+
+    /// Top-left corner of the pane within the window, in physical pixels.
+    fn physical_origin(&self, scale_factor: f32) -> (f32, f32) {
+        if self.anchor != PaneAnchor::None {
+            (self.last_physical_rect.origin.x as f32, self.last_physical_rect.origin.y as f32)
+        } else {
+            let physical_rect = self.rect.scale(scale_factor, scale_factor);
+            (physical_rect.origin.x.round(), physical_rect.origin.y.round())
+        }
+    }
 }
 
 fn dispatch_to_renderer(window_id: String, pane_id: Option<String>, data: axum::body::Bytes) {
@@ -1437,12 +2142,27 @@ fn dispatch_to_renderer(window_id: String, pane_id: Option<String>, data: axum::
     }
 }
 
+#[derive(Debug, Clone, Copy)]
+pub enum Navigation {
+    Back,
+    Forward,
+    Reload,
+    Stop,
+}
+
 #[derive(Debug)]
 pub enum EngineCommand {
     Wake,
     // App-level commands
     CreateWindow(WindowOptions, String), // options, window_id
     Quit,
+    RefreshDisplays,
+    Tray(String, tray::TrayCommand), // tray_id, command
+    TrayEvent(String, tray::TrayEvent), // tray_id, event from the platform tray host
+    RegisterGlobalShortcut(String, global_shortcut::Reply), // accelerator, reply
+    UnregisterGlobalShortcut(String, global_shortcut::Reply), // accelerator, reply
+    GlobalShortcutsReady, // the X11 grab connection has events to read
+    GlobalShortcut(global_shortcut::ShortcutEvent), // event from the portal
 
     // Window-specific commands (all take window ID)
     LoadUrl(String, String, String), // window_id, pane_id, url
@@ -1468,6 +2188,23 @@ pub enum EngineCommand {
     NewFrameReady(String, String), // window_id, pane_id
     SetMinInnerSize(String, Option<winit::dpi::PhysicalSize<u32>>), // window_id, size (None = remove constraint)
     SetMaxInnerSize(String, Option<winit::dpi::PhysicalSize<u32>>), // window_id, size (None = remove constraint)
+    SetFullscreen(String, FullscreenMode), // window_id, mode
+    QueryWindow(String, tokio::sync::oneshot::Sender<Option<WindowSnapshot>>), // window_id, reply
+    SetIgnoreMouseEvents(String, bool, bool), // window_id, ignore, forward_hover
+    SetOpacity(String, f32), // window_id, opacity (0.0 - 1.0)
+    SetIcon(String, Option<winit::window::Icon>), // window_id, icon (None = platform default)
+    SetMenu(String, Option<(String, Vec<menu::MenuItemOptions>)>), // window_id, (menu_id, items) or None to remove
+    PopupMenu(String, String, String, Vec<menu::MenuItemOptions>, Option<(f64, f64)>), // window_id, pane_id, menu_id, items, pane-relative position
+    MenuActivated(String, menu::MenuActivation), // window_id, activation from a native menu
+    BeginDialog(String, tokio::sync::oneshot::Sender<Option<dialog::DialogParent>>), // window_id, reply with parent handles
+    EndDialog(String), // window_id
+    RegisterAccelerator(String, accelerator::Accelerator, String), // window_id, parsed, as registered
+    UnregisterAccelerator(String, accelerator::Accelerator), // window_id, parsed
+    SetBeforeInput(String, bool, Option<u32>), // window_id, enabled, budget in ms
+    ResolveInput(String, u32, bool), // window_id, seq, prevent
+    ShowIme(String, String, servo::webrender_api::units::DeviceIntRect), // window_id, pane_id, caret rect in the pane
+    HideIme(String, String), // window_id, pane_id
+    NotificationEvent(String, notification::NotificationSource, notification::NotificationEvent), // notification_id, source, event
 
     // Pane-specific commands
     CreatePane(String, String, String, euclid::Rect<f32, servo::DeviceIndependentPixel>, i32, PaneAnchor, u32), // window_id, pane_id, url, rect, z_index, anchor, dock_order
@@ -1475,6 +2212,20 @@ pub enum EngineCommand {
     SetPaneRect(String, String, euclid::Rect<f32, servo::DeviceIndependentPixel>), // window_id, pane_id, rect
     SetPaneVisible(String, String, bool), // window_id, pane_id, visible
     FocusPane(String, String), // window_id, pane_id
+    Navigate(String, String, Navigation), // window_id, pane_id, action
+    HistoryChanged(String, String, Vec<String>, usize), // window_id, pane_id, entries, current index
+    NavigationBlocked(String, String, navigation::PendingNavigation), // window_id, pane_id, request
+    ResolveNavigation(String, u32, String), // window_id, seq, "allow" | "deny" | "external"
+    SetAllowedOrigins(String, Option<String>, Option<Vec<String>>), // window_id, pane_id (None = the window's), origins (None = any)
+    SetCrashPolicy(String, Option<String>, crash::CrashPolicy), // window_id, pane_id (None = the window's), policy
+    LoadFailed(String, String, navigation::LoadFailure), // window_id, pane_id, failure
+    ResolvePermission(String, u32, bool, bool), // window_id, seq, allow, remember for the origin
+    ResolveLogin(String, u32, Option<(String, String)>, bool), // window_id, seq, username and password (None cancels), remember for the session
+    ResolvePopup(String, u32, Option<(euclid::Rect<f32, servo::DeviceIndependentPixel>, i32)>), // window_id, seq, pane rect and z-index; None closes it
+    SetPaneZoom(String, String, f32), // window_id, pane_id, zoom factor
+    RestorePaneZoom(String, String), // window_id, pane_id: apply the saved level for its origin
+    CyclePaneFocus(String, bool), // window_id, backwards
+    SetFocusOrder(String, Vec<String>), // window_id, pane ids
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -1485,6 +2236,17 @@ pub struct DragRect {
     pub height: f32,
 }
 
+#[derive(Debug, serde::Deserialize)]
+pub struct LoadFailedPayload {
+    pub nonce: String,
+    #[serde(rename = "paneKey")]
+    pub pane_key: String,
+    pub url: String,
+    pub reason: String,
+    #[serde(rename = "mainFrame")]
+    pub main_frame: bool,
+}
+
 #[derive(Debug, serde::Deserialize)]
 pub struct DragRegionPayload {
     #[serde(rename = "paneId")]
@@ -1495,7 +2257,7 @@ pub struct DragRegionPayload {
     pub no_drag: Option<Vec<DragRect>>,
 }
 
-fn intercept_drag_regions(raw_bytes: &[u8], window_id: String) {
+fn intercept_control_messages(raw_bytes: &[u8], window_id: String) {
     let mut cursor = std::io::Cursor::new(raw_bytes);
     match rmpv::decode::value::read_value(&mut cursor) {
         Ok(batch_val) => {
@@ -1540,6 +2302,27 @@ fn intercept_drag_regions(raw_bytes: &[u8], window_id: String) {
                                                 debug!("Rust: Failed to deserialize DragRegionPayload: {:?}", e);
                                             }
                                         }
+                                    } else if channel == "lotus:load-failed" {
+                                        // Sent by resources/neterror.html and badcert.html. Only
+                                        // those carry the nonce, and the pane comes from its key,
+                                        // never from what the sender claims.
+                                        if let Ok(payload) = rmpv::ext::from_value::<LoadFailedPayload>(pair[1].clone()) {
+                                            if payload.nonce != *ERROR_PAGE_NONCE {
+                                                warn!("Rust: Ignored load-failed report without the error page nonce");
+                                                continue;
+                                            }
+                                            let pane = APP_STATE.get()
+                                                .and_then(|state| state.lock().ok())
+                                                .and_then(|s| s.pane_keys.get(&payload.pane_key).cloned());
+                                            let Some((window_id, pane_id)) = pane else { continue };
+                                            let failure = navigation::LoadFailure {
+                                                code: navigation::error_code(&payload.reason).to_string(),
+                                                url: payload.url,
+                                                description: payload.reason,
+                                                main_frame: payload.main_frame,
+                                            };
+                                            let _ = proxy.send_event(EngineCommand::LoadFailed(window_id, pane_id, failure));
+                                        }
                                     }
                                 }
                             }
@@ -1768,6 +2551,70 @@ impl WindowHandle {
         }
     }
 
+    #[napi]
+    pub fn go_back_in_pane(&self, pane_id: String) {
+        self.navigate(pane_id, Navigation::Back);
+    }
+
+    #[napi]
+    pub fn go_forward_in_pane(&self, pane_id: String) {
+        self.navigate(pane_id, Navigation::Forward);
+    }
+
+    #[napi]
+    pub fn reload_pane(&self, pane_id: String) {
+        self.navigate(pane_id, Navigation::Reload);
+    }
+
+    #[napi]
+    pub fn stop_pane(&self, pane_id: String) {
+        self.navigate(pane_id, Navigation::Stop);
+    }
+
+    #[napi]
+    pub async fn can_go_back_in_pane(&self, pane_id: String) -> napi::Result<bool> {
+        Ok(query_pane(self.id.clone(), &pane_id).await?.can_go_back)
+    }
+
+    #[napi]
+    pub async fn can_go_forward_in_pane(&self, pane_id: String) -> napi::Result<bool> {
+        Ok(query_pane(self.id.clone(), &pane_id).await?.can_go_forward)
+    }
+
+    /// Page zoom for a pane (1.0 = 100%), clamped to 0.25 - 5.0. Layout and input
+    /// coordinates stay in device pixels; Servo scales the page's CSS pixels.
+    #[napi]
+    pub fn set_pane_zoom(&self, pane_id: String, zoom: f64) -> napi::Result<()> {
+        if !zoom.is_finite() {
+            return Err(napi::Error::from_reason(format!("Invalid zoom factor: {}", zoom)));
+        }
+        if let Some(proxy) = EVENT_LOOP_PROXY.get() {
+            let _ = proxy.send_event(EngineCommand::SetPaneZoom(self.id.clone(), pane_id, zoom as f32));
+        }
+        Ok(())
+    }
+
+    #[napi]
+    pub async fn get_pane_zoom(&self, pane_id: String) -> napi::Result<f64> {
+        Ok(query_pane(self.id.clone(), &pane_id).await?.zoom)
+    }
+
+    /// Move focus to the next pane in tab order, as Ctrl+F6 does.
+    #[napi]
+    pub fn focus_next_pane(&self, backwards: Option<bool>) {
+        if let Some(proxy) = EVENT_LOOP_PROXY.get() {
+            let _ = proxy.send_event(EngineCommand::CyclePaneFocus(self.id.clone(), backwards.unwrap_or(false)));
+        }
+    }
+
+    /// Pane ids in keyboard traversal order. Panes left out follow, by position.
+    #[napi]
+    pub fn set_focus_order(&self, pane_ids: Vec<String>) {
+        if let Some(proxy) = EVENT_LOOP_PROXY.get() {
+            let _ = proxy.send_event(EngineCommand::SetFocusOrder(self.id.clone(), pane_ids));
+        }
+    }
+
     #[napi]
     pub fn minimize(&self) {
         if let Some(proxy) = EVENT_LOOP_PROXY.get() {
@@ -1830,67 +2677,400 @@ impl WindowHandle {
             let _ = proxy.send_event(EngineCommand::SetMaxInnerSize(self.id.clone(), size));
         }
     }
-}
 
+    /// Let mouse clicks pass through to whatever is behind the window.
+    /// With `forward_hover`, panes still receive cursor movement while ignoring.
+    #[napi]
+    pub fn set_ignore_mouse_events(&self, ignore: bool, forward_hover: Option<bool>) {
+        if let Some(proxy) = EVENT_LOOP_PROXY.get() {
+            let _ = proxy.send_event(EngineCommand::SetIgnoreMouseEvents(self.id.clone(), ignore, forward_hover.unwrap_or(false)));
+        }
+    }
 
+    /// Whole-window opacity, 0.0 (invisible) to 1.0 (opaque).
+    #[napi]
+    pub fn set_opacity(&self, opacity: f64) {
+        if let Some(proxy) = EVENT_LOOP_PROXY.get() {
+            let _ = proxy.send_event(EngineCommand::SetOpacity(self.id.clone(), opacity as f32));
+        }
+    }
 
-
-// ------------------------------------------------------------------
-// RESOURCES IMPLEMENTATION
-// ------------------------------------------------------------------
-
-struct ResourceReader;
-
-impl resources::ResourceReaderMethods for ResourceReader {
-    fn read(&self, file: Resource) -> Vec<u8> {
-        let mut path = resources_dir_path().clone();
-        path.push(file.filename());
-        // debug!("Rust: Reading resource: {:?}", path); 
-        match fs::read(&path) {
-            Ok(data) => data,
-            Err(e) => {
-                eprintln!("Rust Warning: Missing resource: {:?} ({})", path, e);
-                Vec::new()
+    /// Replace the window icon with PNG data or a PNG path (disk or VFS).
+    /// Pass nothing to go back to the platform default.
+    #[napi]
+    pub fn set_icon(&self, icon: Option<napi::Either<napi::bindgen_prelude::Buffer, String>>) -> napi::Result<()> {
+        let decoded = match icon {
+            None => None,
+            Some(napi::Either::A(buffer)) => Some(icon::decode_png(&buffer).map_err(napi::Error::from_reason)?),
+            Some(napi::Either::B(path)) => {
+                let root = APP_STATE.get()
+                    .and_then(|state| state.lock().ok())
+                    .and_then(|s| s.window_metadata.get(&self.id).and_then(|m| m.root_path.clone()));
+                Some(icon::load_png(&path, root.as_deref()).map_err(napi::Error::from_reason)?)
             }
+        };
+        if let Some(proxy) = EVENT_LOOP_PROXY.get() {
+            let _ = proxy.send_event(EngineCommand::SetIcon(self.id.clone(), decoded));
         }
+        Ok(())
     }
-    fn sandbox_access_files_dirs(&self) -> Vec<PathBuf> {
-        vec![resources_dir_path().clone()]
-    }
-    fn sandbox_access_files(&self) -> Vec<PathBuf> {
-        vec![]
+
+    /// Attach a menu bar. Fails where none can be shown (see `menuBarSupported`).
+    #[napi]
+    pub fn set_menu(&self, menu_id: String, items: Vec<menu::MenuItemOptions>) -> napi::Result<()> {
+        menu::bar_support().map_err(|e| napi::Error::from_reason(format!("Menu bar unavailable: {}", e)))?;
+        if let Some(proxy) = EVENT_LOOP_PROXY.get() {
+            let _ = proxy.send_event(EngineCommand::SetMenu(self.id.clone(), Some((menu_id, items))));
+        }
+        Ok(())
     }
-}
 
-static RESOURCE_READER: ResourceReader = ResourceReader;
-servo::submit_resource_reader!(&RESOURCE_READER);
+    #[napi]
+    pub fn remove_menu(&self) {
+        if let Some(proxy) = EVENT_LOOP_PROXY.get() {
+            let _ = proxy.send_event(EngineCommand::SetMenu(self.id.clone(), None));
+        }
+    }
 
-static RESOURCES_DIR: once_cell::sync::Lazy<PathBuf> = once_cell::sync::Lazy::new(|| {
-    let mut path = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-    path.push("resources");
-    if path.exists() {
-        return path;
+    /// Show a context menu over a pane. `x`/`y` are logical pixels relative to the
+    /// pane; without them the menu opens at the last pointer position.
+    #[napi]
+    pub fn popup_menu(&self, pane_id: String, menu_id: String, items: Vec<menu::MenuItemOptions>, x: Option<f64>, y: Option<f64>) {
+        let position = x.zip(y);
+        if let Some(proxy) = EVENT_LOOP_PROXY.get() {
+            let _ = proxy.send_event(EngineCommand::PopupMenu(self.id.clone(), pane_id, menu_id, items, position));
+        }
     }
-    // Fallback?
-    path.pop();
-    path
-});
 
-fn resources_dir_path() -> &'static PathBuf {
-    &RESOURCES_DIR
-}
+    /// Handle an accelerator while this window has focus. Matching keys are
+    /// reported as "accelerator" events and never reach the page.
+    #[napi]
+    pub fn register_accelerator(&self, accelerator: String) -> napi::Result<()> {
+        let parsed = accelerator::Accelerator::parse(&accelerator).map_err(napi::Error::from_reason)?;
+        if let Some(proxy) = EVENT_LOOP_PROXY.get() {
+            let _ = proxy.send_event(EngineCommand::RegisterAccelerator(self.id.clone(), parsed, accelerator));
+        }
+        Ok(())
+    }
 
-// ------------------------------------------------------------------
-// DELEGATE IMPLEMENTATIONS
-// ------------------------------------------------------------------
+    #[napi]
+    pub fn unregister_accelerator(&self, accelerator: String) -> napi::Result<()> {
+        let parsed = accelerator::Accelerator::parse(&accelerator).map_err(napi::Error::from_reason)?;
+        if let Some(proxy) = EVENT_LOOP_PROXY.get() {
+            let _ = proxy.send_event(EngineCommand::UnregisterAccelerator(self.id.clone(), parsed));
+        }
+        Ok(())
+    }
 
+    /// Hold each key until Node answers its "before-input" event through
+    /// `resolve_input`, or until `budget_ms` (default 50) has passed.
+    #[napi]
+    pub fn set_before_input(&self, enabled: bool, budget_ms: Option<u32>) {
+        if let Some(proxy) = EVENT_LOOP_PROXY.get() {
+            let _ = proxy.send_event(EngineCommand::SetBeforeInput(self.id.clone(), enabled, budget_ms));
+        }
+    }
 
-struct LotusPaneDelegate {
-    window: Arc<Window>,
+    #[napi]
+    pub fn resolve_input(&self, seq: u32, prevent: bool) {
+        if let Some(proxy) = EVENT_LOOP_PROXY.get() {
+            let _ = proxy.send_event(EngineCommand::ResolveInput(self.id.clone(), seq, prevent));
+        }
+    }
+
+    /// Origins a pane (or, without a pane id, every pane) may navigate to.
+    /// None removes the restriction; a pane's own list wins over the window's.
+    #[napi]
+    pub fn set_allowed_origins(&self, pane_id: Option<String>, origins: Option<Vec<String>>) -> napi::Result<()> {
+        if let Some(origins) = &origins {
+            navigation::check_allowlist(origins).map_err(napi::Error::from_reason)?;
+        }
+        // Through the event loop, so it lands after the window's metadata exists.
+        if let Some(proxy) = EVENT_LOOP_PROXY.get() {
+            let _ = proxy.send_event(EngineCommand::SetAllowedOrigins(self.id.clone(), pane_id, origins));
+        }
+        Ok(())
+    }
+
+    /// Show a `window.open` popup as a pane of this window. The pane id is
+    /// the one given in the `new-window-requested` event.
+    #[napi]
+    pub fn adopt_popup(&self, seq: u32, x: f64, y: f64, width: f64, height: f64, z_index: i32) {
+        if let Some(proxy) = EVENT_LOOP_PROXY.get() {
+            let rect = euclid::Rect::new(euclid::Point2D::new(x as f32, y as f32), euclid::Size2D::new(width as f32, height as f32));
+            let _ = proxy.send_event(EngineCommand::ResolvePopup(self.id.clone(), seq, Some((rect, z_index))));
+        }
+    }
+
+    /// Close a `window.open` popup without showing it.
+    #[napi]
+    pub fn discard_popup(&self, seq: u32) {
+        if let Some(proxy) = EVENT_LOOP_PROXY.get() {
+            let _ = proxy.send_event(EngineCommand::ResolvePopup(self.id.clone(), seq, None));
+        }
+    }
+
+    /// What a pane (or, without a pane id, every pane) does after a crash:
+    /// "keep", "reload" or "show-error-page".
+    #[napi]
+    pub fn set_crash_policy(&self, pane_id: Option<String>, policy: String) -> napi::Result<()> {
+        let policy = crash::CrashPolicy::parse(&policy).map_err(napi::Error::from_reason)?;
+        if let Some(proxy) = EVENT_LOOP_PROXY.get() {
+            let _ = proxy.send_event(EngineCommand::SetCrashPolicy(self.id.clone(), pane_id, policy));
+        }
+        Ok(())
+    }
+
+    /// Answer a `permission-request` event. With `remember`, the answer is
+    /// kept for the origin and later requests don't reach Node.
+    #[napi]
+    pub fn resolve_permission(&self, seq: u32, allow: bool, remember: Option<bool>) {
+        if let Some(proxy) = EVENT_LOOP_PROXY.get() {
+            let _ = proxy.send_event(EngineCommand::ResolvePermission(self.id.clone(), seq, allow, remember.unwrap_or(false)));
+        }
+    }
+
+    /// Answer a `login` event with credentials, or cancel it with none.
+    /// With `remember`, they're reused for the origin until the app exits.
+    #[napi]
+    pub fn resolve_login(&self, seq: u32, username: Option<String>, password: Option<String>, remember: Option<bool>) {
+        if let Some(proxy) = EVENT_LOOP_PROXY.get() {
+            let credentials = username.map(|u| (u, password.unwrap_or_default()));
+            let _ = proxy.send_event(EngineCommand::ResolveLogin(self.id.clone(), seq, credentials, remember.unwrap_or(false)));
+        }
+    }
+
+    /// Answer a `will-navigate` event: "allow", "deny" or "external".
+    #[napi]
+    pub fn resolve_navigation(&self, seq: u32, action: String) {
+        if let Some(proxy) = EVENT_LOOP_PROXY.get() {
+            let _ = proxy.send_event(EngineCommand::ResolveNavigation(self.id.clone(), seq, action));
+        }
+    }
+
+    /// Open-file dialog, modal to this window.
+    #[napi]
+    pub async fn show_open_dialog(&self, options: Option<dialog::OpenDialogOptions>) -> napi::Result<dialog::OpenDialogResult> {
+        dialog::open(Some(self.id.clone()), options.unwrap_or_default()).await
+    }
+
+    #[napi]
+    pub async fn show_save_dialog(&self, options: Option<dialog::SaveDialogOptions>) -> napi::Result<dialog::SaveDialogResult> {
+        dialog::save(Some(self.id.clone()), options.unwrap_or_default()).await
+    }
+
+    #[napi]
+    pub async fn show_message_box(&self, options: dialog::MessageBoxOptions) -> napi::Result<dialog::MessageBoxResult> {
+        dialog::message_box(Some(self.id.clone()), options).await
+    }
+
+    /// Switch fullscreen mode at runtime.
+    /// 0 = windowed, 1 = borderless fullscreen, 2 = exclusive fullscreen.
+    #[napi]
+    pub fn set_fullscreen(&self, mode: u32) {
+        if let Some(proxy) = EVENT_LOOP_PROXY.get() {
+            let _ = proxy.send_event(EngineCommand::SetFullscreen(self.id.clone(), FullscreenMode::from(mode)));
+        }
+    }
+
+    /// Current state of the window as seen by the event loop.
+    #[napi]
+    pub async fn get_state(&self) -> napi::Result<WindowSnapshot> {
+        query_window(self.id.clone()).await
+    }
+
+    #[napi]
+    pub async fn get_bounds(&self) -> napi::Result<WindowBounds> {
+        Ok(query_window(self.id.clone()).await?.bounds)
+    }
+
+    #[napi]
+    pub async fn is_maximized(&self) -> napi::Result<bool> {
+        Ok(query_window(self.id.clone()).await?.maximized)
+    }
+
+    #[napi]
+    pub async fn is_minimized(&self) -> napi::Result<bool> {
+        Ok(query_window(self.id.clone()).await?.minimized)
+    }
+
+    #[napi]
+    pub async fn is_visible(&self) -> napi::Result<bool> {
+        Ok(query_window(self.id.clone()).await?.visible)
+    }
+
+    #[napi]
+    pub async fn is_focused(&self) -> napi::Result<bool> {
+        Ok(query_window(self.id.clone()).await?.focused)
+    }
+
+    #[napi]
+    pub async fn get_scale_factor(&self) -> napi::Result<f64> {
+        Ok(query_window(self.id.clone()).await?.scale_factor)
+    }
+
+    #[napi]
+    pub async fn get_title(&self) -> napi::Result<String> {
+        Ok(query_window(self.id.clone()).await?.title)
+    }
+
+    #[napi]
+    pub async fn get_panes(&self) -> napi::Result<Vec<PaneInfo>> {
+        Ok(query_window(self.id.clone()).await?.panes)
+    }
+}
+
+impl WindowHandle {
+    fn navigate(&self, pane_id: String, action: Navigation) {
+        if let Some(proxy) = EVENT_LOOP_PROXY.get() {
+            let _ = proxy.send_event(EngineCommand::Navigate(self.id.clone(), pane_id, action));
+        }
+    }
+}
+
+async fn query_pane(window_id: String, pane_id: &str) -> napi::Result<PaneInfo> {
+    query_window(window_id).await?.panes.into_iter()
+        .find(|p| p.id == pane_id)
+        .ok_or_else(|| napi::Error::from_reason(format!("Pane {} not found", pane_id)))
+}
+
+/// Round-trip a snapshot request through the event loop. The window lives on the
+/// event loop thread, so this is the only safe way to read its state from Node.
+async fn query_window(window_id: String) -> napi::Result<WindowSnapshot> {
+    let proxy = EVENT_LOOP_PROXY.get()
+        .ok_or_else(|| napi::Error::from_reason("Event loop is not running"))?;
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    proxy.send_event(EngineCommand::QueryWindow(window_id.clone(), tx))
+        .map_err(|_| napi::Error::from_reason("Event loop has shut down"))?;
+    match rx.await {
+        Ok(Some(snapshot)) => Ok(snapshot),
+        Ok(None) => Err(napi::Error::from_reason(format!("Window {} not found", window_id))),
+        Err(_) => Err(napi::Error::from_reason("Event loop dropped the request")),
+    }
+}
+
+
+
+
+// ------------------------------------------------------------------
+// RESOURCES IMPLEMENTATION
+// ------------------------------------------------------------------
+
+struct ResourceReader;
+
+/// Stamped into Servo's network and certificate error pages so their
+/// load-failed reports can be told apart from a page sending its own.
+static ERROR_PAGE_NONCE: once_cell::sync::Lazy<String> = once_cell::sync::Lazy::new(|| Uuid::new_v4().to_string());
+
+/// Register a fresh key for a pane and return the user script that gives it
+/// to the pane's documents, including Servo's error pages.
+fn pane_key_script(window_id: &str, pane_id: &str) -> String {
+    let key = Uuid::new_v4().to_string();
+    if let Some(mut s) = APP_STATE.get().and_then(|state| state.lock().ok()) {
+        s.pane_keys.retain(|_, (w, p)| !(w == window_id && p == pane_id));
+        s.pane_keys.insert(key.clone(), (window_id.to_string(), pane_id.to_string()));
+    }
+    format!("window.lotus.paneKey = '{}';", key)
+}
+
+impl resources::ResourceReaderMethods for ResourceReader {
+    fn read(&self, file: Resource) -> Vec<u8> {
+        let stamp_nonce = matches!(file, Resource::NetErrorHTML | Resource::BadCertHTML);
+        let mut path = resources_dir_path().clone();
+        path.push(file.filename());
+        // debug!("Rust: Reading resource: {:?}", path); 
+        match fs::read(&path) {
+            Ok(data) if stamp_nonce => String::from_utf8_lossy(&data)
+                .replace("${lotus_nonce}", &ERROR_PAGE_NONCE)
+                .into_bytes(),
+            Ok(data) => data,
+            Err(e) => {
+                eprintln!("Rust Warning: Missing resource: {:?} ({})", path, e);
+                Vec::new()
+            }
+        }
+    }
+    fn sandbox_access_files_dirs(&self) -> Vec<PathBuf> {
+        vec![resources_dir_path().clone()]
+    }
+    fn sandbox_access_files(&self) -> Vec<PathBuf> {
+        vec![]
+    }
+}
+
+static RESOURCE_READER: ResourceReader = ResourceReader;
+servo::submit_resource_reader!(&RESOURCE_READER);
+
+static RESOURCES_DIR: once_cell::sync::Lazy<PathBuf> = once_cell::sync::Lazy::new(|| {
+    let mut path = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    path.push("resources");
+    if path.exists() {
+        return path;
+    }
+    // Fallback?
+    path.pop();
+    path
+});
+
+fn resources_dir_path() -> &'static PathBuf {
+    &RESOURCES_DIR
+}
+
+// ------------------------------------------------------------------
+// DELEGATE IMPLEMENTATIONS
+// ------------------------------------------------------------------
+
+
+struct LotusPaneDelegate {
+    window: Arc<Window>,
     window_id: String,
     pane_id: String,
     proxy: EventLoopProxy<EngineCommand>,
     ready_to_repaint: Arc<AtomicBool>,
+    popup_host: popup::PopupHost,
+}
+
+impl LotusPaneDelegate {
+    /// Whether a lotus-resource request is this window's error page, opened
+    /// with failure details to render into it.
+    fn is_error_page(&self, url: &url::Url, relative_path: &str) -> bool {
+        url.query_pairs().any(|(k, _)| k == "code") && APP_STATE.get()
+            .and_then(|state| state.lock().ok())
+            .is_some_and(|s| s.window_metadata.get(&self.window_id)
+                .and_then(|m| m.error_page.as_deref())
+                .is_some_and(|page| page == relative_path))
+    }
+
+    fn report_load_failure(&self, url: &url::Url, code: &str, description: &str, main_frame: bool) {
+        let _ = self.proxy.send_event(EngineCommand::LoadFailed(self.window_id.clone(), self.pane_id.clone(), navigation::LoadFailure {
+            url: url.to_string(),
+            code: code.to_string(),
+            description: description.to_string(),
+            main_frame,
+        }));
+    }
+}
+
+/// new-window-requested for a parked popup, sent to its opener pane.
+fn announce_popup(proxy: &EventLoopProxy<EngineCommand>, window_id: &str, popup_pane_id: &str, announcement: popup::Announcement) {
+    let Some(seq) = popup_pane_id.strip_prefix("popup-").and_then(|s| s.parse::<u32>().ok()) else { return };
+    emit_to_pane(proxy, format!("{}:{}", window_id, announcement.opener_pane), serde_json::json!({
+        "event": "new-window-requested",
+        "window_id": window_id,
+        "seq": seq,
+        "url": announcement.url,
+        "target": announcement.hints.target,
+        "features": announcement.hints.features,
+        "popup_pane_id": popup_pane_id
+    }));
+}
+
+/// Send an event to Node under a pane's "window_id:pane_id" client id.
+fn emit_to_pane(proxy: &EventLoopProxy<EngineCommand>, client_id: String, payload: serde_json::Value) {
+    let mut msg = vec![MSG_TYPE_DATA];
+    if rmp_serde::encode::write(&mut msg, &payload).is_ok() {
+        let _ = proxy.send_event(EngineCommand::IpcMessage(client_id, msg));
+    }
 }
 
 impl WebViewDelegate for LotusPaneDelegate {
@@ -1920,6 +3100,10 @@ impl WebViewDelegate for LotusPaneDelegate {
             LoadStatus::Complete => "complete",
         };
 
+        if status == LoadStatus::HeadParsed {
+            let _ = self.proxy.send_event(EngineCommand::RestorePaneZoom(self.window_id.clone(), self.pane_id.clone()));
+        }
+
         if status == LoadStatus::Started {
             let client_id = format!("{}:{}", self.window_id, self.pane_id);
             if let Some(pending) = WS_PENDING.get() {
@@ -1968,8 +3152,303 @@ impl WebViewDelegate for LotusPaneDelegate {
         }
     }
 
-    fn show_console_message(&self, _webview: servo::WebView, _level: ConsoleLogLevel, message: String) {
-        info!("Rust Console [{}|{}]: {}", self.window_id, self.pane_id, message);
+    fn notify_url_changed(&self, _webview: servo::WebView, url: url::Url) {
+        emit_to_pane(&self.proxy, format!("{}:{}", self.window_id, self.pane_id), serde_json::json!({
+            "event": "url-changed",
+            "window_id": self.window_id,
+            "url": url.as_str()
+        }));
+    }
+
+    fn notify_history_changed(&self, _webview: servo::WebView, entries: Vec<url::Url>, current: usize) {
+        let entries = entries.into_iter().map(String::from).collect();
+        let _ = self.proxy.send_event(EngineCommand::HistoryChanged(self.window_id.clone(), self.pane_id.clone(), entries, current));
+    }
+
+    fn notify_favicon_changed(&self, webview: servo::WebView) {
+        let Some(favicon) = webview.favicon() else { return };
+        let (width, height) = (favicon.width, favicon.height);
+        let data = favicon.data();
+        let rgba: Vec<u8> = match favicon.format {
+            servo::PixelFormat::RGBA8 => data.to_vec(),
+            servo::PixelFormat::BGRA8 => data.chunks_exact(4).flat_map(|p| [p[2], p[1], p[0], p[3]]).collect(),
+            servo::PixelFormat::RGB8 => data.chunks_exact(3).flat_map(|p| [p[0], p[1], p[2], 255]).collect(),
+            servo::PixelFormat::KA8 => data.chunks_exact(2).flat_map(|p| [p[0], p[0], p[0], p[1]]).collect(),
+            servo::PixelFormat::K8 => data.iter().flat_map(|&k| [k, k, k, 255]).collect(),
+        };
+        match icon::encode_png_rgba(&rgba, width, height) {
+            Ok(png) => {
+                use base64::Engine;
+                emit_to_pane(&self.proxy, format!("{}:{}", self.window_id, self.pane_id), serde_json::json!({
+                    "event": "favicon-changed",
+                    "window_id": self.window_id,
+                    "favicon": format!("data:image/png;base64,{}", base64::engine::general_purpose::STANDARD.encode(png))
+                }));
+            }
+            Err(e) => warn!("Rust: Favicon for {} pane {} not encoded: {}", self.window_id, self.pane_id, e),
+        }
+    }
+
+    fn notify_crashed(&self, webview: servo::WebView, reason: String, backtrace: Option<String>) {
+        error!("Rust: {} pane {} crashed: {}", self.window_id, self.pane_id, reason);
+
+        // Reload, unless the last crash reload was moments ago; then the error page.
+        let policy = APP_STATE.get()
+            .and_then(|state| state.lock().ok())
+            .and_then(|mut s| {
+                let meta = s.window_metadata.get_mut(&self.window_id)?;
+                let mut policy = meta.crash_policy(&self.pane_id);
+                if policy == crash::CrashPolicy::Reload {
+                    let now = Instant::now();
+                    let recent = meta.crash_reloads.get(&self.pane_id).is_some_and(|t| now.duration_since(*t) < crash::RELOAD_GRACE);
+                    if recent {
+                        policy = crash::CrashPolicy::ShowErrorPage;
+                    } else {
+                        meta.crash_reloads.insert(self.pane_id.clone(), now);
+                    }
+                }
+                Some(policy)
+            })
+            .unwrap_or_default();
+
+        emit_to_pane(&self.proxy, format!("{}:{}", self.window_id, self.pane_id), serde_json::json!({
+            "event": "pane-crashed",
+            "window_id": self.window_id,
+            "reason": reason,
+            "backtrace": backtrace,
+            "action": policy.as_str()
+        }));
+
+        match policy {
+            crash::CrashPolicy::Keep => {},
+            crash::CrashPolicy::Reload => webview.reload(),
+            crash::CrashPolicy::ShowErrorPage => {
+                if let Ok(url) = url::Url::parse(&crash::error_page_url(&reason)) {
+                    webview.load(url);
+                }
+            },
+        }
+    }
+
+    fn notify_fullscreen_state_changed(&self, _webview: servo::WebView, fullscreen: bool) {
+        // Page called element.requestFullscreen() / document.exitFullscreen().
+        // Page-initiated fullscreen is always borderless; exclusive is reserved for Node.
+        info!("Rust: Page requested fullscreen={} for {} pane {}", fullscreen, self.window_id, self.pane_id);
+        let mode = if fullscreen { FullscreenMode::Borderless } else { FullscreenMode::None };
+        let _ = self.proxy.send_event(EngineCommand::SetFullscreen(self.window_id.clone(), mode));
+    }
+
+    fn request_navigation(&self, _webview: servo::WebView, request: servo::NavigationRequest) {
+        // A popup waiting on Node doesn't load anything until it's adopted.
+        let request = match popup::hold_navigation(&self.window_id, &self.pane_id, request) {
+            Ok(announcement) => {
+                if let Some(announcement) = announcement {
+                    announce_popup(&self.proxy, &self.window_id, &self.pane_id, announcement);
+                }
+                return;
+            }
+            Err(request) => request,
+        };
+
+        let allowed = APP_STATE.get()
+            .and_then(|state| state.lock().ok())
+            .map(|s| match s.window_metadata.get(&self.window_id).and_then(|m| m.allowed_origins(&self.pane_id)) {
+                Some(origins) => navigation::origin_allowed(&request.url, origins),
+                None => true,
+            })
+            .unwrap_or(true);
+
+        if allowed {
+            request.allow();
+        } else {
+            info!("Rust: Navigation to {} held for {} pane {}", request.url, self.window_id, self.pane_id);
+            let _ = self.proxy.send_event(EngineCommand::NavigationBlocked(
+                self.window_id.clone(), self.pane_id.clone(), navigation::PendingNavigation::new(request)));
+        }
+    }
+
+    fn request_open_auxiliary_webview(&self, parent: servo::WebView) -> Option<servo::WebView> {
+        // Created hidden at 1x1; it only gets a rect if Node adopts it as a pane.
+        let seq = popup::next_seq();
+        let pane_id = popup::pane_id(seq);
+        let offscreen_ctx = Rc::new(self.popup_host.rendering_context.offscreen_context(ServoPhysicalSize::new(1, 1)));
+        let ready_to_repaint = Arc::new(AtomicBool::new(true));
+        let delegate = Rc::new(LotusPaneDelegate {
+            window: self.window.clone(),
+            window_id: self.window_id.clone(),
+            pane_id: pane_id.clone(),
+            proxy: self.proxy.clone(),
+            ready_to_repaint: ready_to_repaint.clone(),
+            popup_host: self.popup_host.clone(),
+        });
+        let webview = WebViewBuilder::new_auxiliary(&self.popup_host.servo, offscreen_ctx.clone())
+            .delegate(delegate)
+            .hidpi_scale_factor(Scale::new(self.window.scale_factor() as f32))
+            .build();
+        info!("Rust: Popup {} opened by {} pane {}", pane_id, self.window_id, self.pane_id);
+
+        popup::park(seq, popup::Popup {
+            window_id: self.window_id.clone(),
+            opener_pane: self.pane_id.clone(),
+            webview: webview.clone(),
+            offscreen_ctx,
+            ready_to_repaint,
+            navigation: None,
+            hints: None,
+            announced: false,
+        });
+
+        // Node hears about the popup once its URL is known from its first
+        // navigation, or right away for a bare window.open(), which never
+        // navigates. The opener is still inside window.open, so its recorded
+        // arguments are read once that returns.
+        let proxy = self.proxy.clone();
+        let window_id = self.window_id.clone();
+        parent.evaluate_javascript(POPUP_OPEN_SCRIPT, move |result| {
+            let call = match result {
+                Ok(servo::JSValue::String(json)) => serde_json::from_str::<serde_json::Value>(&json).unwrap_or_default(),
+                _ => serde_json::Value::Null,
+            };
+            let field = |key: &str| call.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+            let hints = popup::OpenHints {
+                target: field("target"),
+                features: field("features"),
+                // Without a record, wait for a navigation rather than announce a blank popup.
+                navigates: call.get("navigates").and_then(|v| v.as_bool()).unwrap_or(true),
+            };
+            if let Some(announcement) = popup::set_hints(seq, hints) {
+                announce_popup(&proxy, &window_id, &pane_id, announcement);
+            }
+        });
+        Some(webview)
+    }
+
+    fn request_permission(&self, webview: servo::WebView, request: servo::PermissionRequest) {
+        let permission = permission::name(&request.feature());
+        let origin = webview.url().map(|url| url_origin(url.as_str())).unwrap_or_default();
+        let seq = permission::hold(&self.window_id, &origin, permission, request);
+
+        // Servo doesn't say which frame asked. Remembered answers for the
+        // top-level origin only apply, and new ones are only offered to be
+        // remembered, when the document has no frames that could have asked.
+        let proxy = self.proxy.clone();
+        let client_id = format!("{}:{}", self.window_id, self.pane_id);
+        let window_id = self.window_id.clone();
+        webview.evaluate_javascript(permission::FRAME_COUNT_SCRIPT, move |result| {
+            let top_level = matches!(result, Ok(servo::JSValue::Number(n)) if n == 0.0);
+            if top_level {
+                let remembered = APP_STATE.get()
+                    .and_then(|state| state.lock().ok())
+                    .and_then(|s| s.permissions.get(&origin, permission));
+                if let Some(allow) = remembered {
+                    if let Some(pending) = permission::take(&window_id, seq) {
+                        pending.answer(allow);
+                    }
+                    return;
+                }
+                permission::set_rememberable(seq);
+            }
+            emit_to_pane(&proxy, client_id, serde_json::json!({
+                "event": "permission-request",
+                "window_id": window_id,
+                "seq": seq,
+                "origin": origin,
+                "permission": permission,
+                "can_remember": top_level
+            }));
+        });
+    }
+
+    fn request_authentication(&self, _webview: servo::WebView, request: servo::AuthenticationRequest) {
+        let url = request.url().clone();
+        let origin = url_origin(url.as_str());
+        let for_proxy = request.for_proxy();
+        let key = auth::CredentialKey::new(&origin, for_proxy);
+
+        let cached = APP_STATE.get()
+            .and_then(|state| state.lock().ok())
+            .and_then(|mut s| s.credentials.lookup(&key, url.as_str()));
+        if let Some((username, password)) = cached {
+            request.authenticate(username, password);
+            return;
+        }
+
+        let seq = auth::hold(auth::PendingLogin {
+            window_id: self.window_id.clone(),
+            key,
+            url: url.to_string(),
+            request,
+        });
+        emit_to_pane(&self.proxy, format!("{}:{}", self.window_id, self.pane_id), serde_json::json!({
+            "event": "login",
+            "window_id": self.window_id,
+            "seq": seq,
+            "url": url.as_str(),
+            "origin": origin,
+            "is_proxy": for_proxy
+        }));
+    }
+
+    fn show_ime(&self, _webview: servo::WebView, _input_type: InputMethodType, _text: Option<(String, i32)>, _multiline: bool, position: servo::webrender_api::units::DeviceIntRect) {
+        let _ = self.proxy.send_event(EngineCommand::ShowIme(self.window_id.clone(), self.pane_id.clone(), position));
+    }
+
+    fn hide_ime(&self, _webview: servo::WebView) {
+        let _ = self.proxy.send_event(EngineCommand::HideIme(self.window_id.clone(), self.pane_id.clone()));
+    }
+
+    fn show_notification(&self, _webview: servo::WebView, notification: servo::Notification) {
+        let root = APP_STATE.get()
+            .and_then(|state| state.lock().ok())
+            .and_then(|s| s.window_metadata.get(&self.window_id).and_then(|m| m.root_path.clone()));
+        // Only icons we can read locally: files and lotus-resource:// paths.
+        let icon = notification.icon_url.as_ref().and_then(|url| match url.scheme() {
+            "file" => url.as_url().to_file_path().ok().map(|p| p.to_string_lossy().into_owned()),
+            "lotus-resource" => Some(url.path().trim_start_matches('/').to_string()),
+            _ => None,
+        });
+        let source = notification::NotificationSource::Page {
+            window_id: self.window_id.clone(),
+            pane_id: self.pane_id.clone(),
+            title: notification.title.clone(),
+            tag: notification.tag.clone(),
+        };
+        let options = notification::NotificationOptions {
+            title: notification.title,
+            body: Some(notification.body).filter(|b| !b.is_empty()),
+            icon,
+            actions: Some(notification.actions.into_iter()
+                .map(|a| notification::NotificationAction { id: a.name, label: a.title })
+                .collect()),
+            require_interaction: Some(notification.require_interaction),
+            silent: notification.silent,
+        };
+        let id = notification::show(source, options, root);
+        info!("Rust: Page notification {} from {} pane {}", id, self.window_id, self.pane_id);
+    }
+
+    fn show_console_message(&self, _webview: servo::WebView, level: ConsoleLogLevel, message: String) {
+        let level = match level {
+            ConsoleLogLevel::Log => "log",
+            ConsoleLogLevel::Debug => "debug",
+            ConsoleLogLevel::Info => "info",
+            ConsoleLogLevel::Warn => "warn",
+            ConsoleLogLevel::Error => "error",
+            ConsoleLogLevel::Trace => "trace",
+        };
+        match level {
+            "debug" | "trace" => debug!("Rust Console [{}|{}]: {}", self.window_id, self.pane_id, message),
+            "warn" => warn!("Rust Console [{}|{}]: {}", self.window_id, self.pane_id, message),
+            "error" => error!("Rust Console [{}|{}]: {}", self.window_id, self.pane_id, message),
+            _ => info!("Rust Console [{}|{}]: {}", self.window_id, self.pane_id, message),
+        }
+        // Servo doesn't pass the calling script's URL or line to the embedder.
+        emit_to_pane(&self.proxy, format!("{}:{}", self.window_id, self.pane_id), serde_json::json!({
+            "event": "console-message",
+            "window_id": self.window_id,
+            "level": level,
+            "message": message
+        }));
     }
 
     fn load_web_resource(&self, _webview: servo::WebView, load: WebResourceLoad) {
@@ -1980,6 +3459,7 @@ impl WebViewDelegate for LotusPaneDelegate {
             let path_in_url = url.path();
             let relative_path = path_in_url.trim_start_matches('/');
             let path_buf = PathBuf::from(relative_path);
+            let is_error_page = self.is_error_page(&url, relative_path);
 
             // 1. Check LRU Cache
             if let Some(state) = APP_STATE.get() {
@@ -1990,11 +3470,12 @@ impl WebViewDelegate for LotusPaneDelegate {
                         if let Ok(val) = HeaderValue::from_str(mime_str) {
                              headers.insert(CONTENT_TYPE, val);
                         }
+                        let data = if is_error_page { navigation::render_error_page(data, &url) } else { data.clone() };
                         let response = WebResourceResponse::new(url)
                             .headers(headers)
                             .status_code(StatusCode::OK);
                         let mut intercepted = load.intercept(response);
-                        intercepted.send_body_data(data.clone());
+                        intercepted.send_body_data(data);
                         intercepted.finish();
                         return;
                     }
@@ -2023,6 +3504,7 @@ impl WebViewDelegate for LotusPaneDelegate {
                     }
                 }
 
+                let data = if is_error_page { navigation::render_error_page(&data, &url) } else { data };
                 let mut headers = HeaderMap::new();
                 if let Ok(val) = HeaderValue::from_str(&mime_str) {
                      headers.insert(CONTENT_TYPE, val);
@@ -2056,6 +3538,7 @@ impl WebViewDelegate for LotusPaneDelegate {
                      (Ok(canonical_full), Ok(canonical_root)) => {
                          if !canonical_full.starts_with(&canonical_root) {
                              warn!("Rust: Blocked directory traversal attempt for {:?}", full_path);
+                             self.report_load_failure(&url, "ERR_ACCESS_DENIED", "Access denied", load.request().is_for_main_frame);
                              let response = WebResourceResponse::new(url)
                                  .status_code(StatusCode::FORBIDDEN);
                              load.intercept(response).finish();
@@ -2075,6 +3558,7 @@ impl WebViewDelegate for LotusPaneDelegate {
                                      }
                                  }
 
+                                 let data = if is_error_page { navigation::render_error_page(&data, &url) } else { data };
                                  let mut headers = HeaderMap::new();
                                  if let Ok(val) = HeaderValue::from_str(&mime_str) {
                                       headers.insert(CONTENT_TYPE, val);
@@ -2089,6 +3573,7 @@ impl WebViewDelegate for LotusPaneDelegate {
                              },
                              Err(e) => {
                                  error!("Failed to read file {:?}: {}", canonical_full, e);
+                                 self.report_load_failure(&url, "ERR_FILE_NOT_FOUND", &e.to_string(), load.request().is_for_main_frame);
                                  let response = WebResourceResponse::new(url)
                                      .status_code(StatusCode::NOT_FOUND);
                                  load.intercept(response).finish();
@@ -2097,6 +3582,7 @@ impl WebViewDelegate for LotusPaneDelegate {
                      },
                      _ => {
                          debug!("Rust: Resource not found: {:?}", full_path);
+                         self.report_load_failure(&url, "ERR_FILE_NOT_FOUND", "File not found", load.request().is_for_main_frame);
                          let response = WebResourceResponse::new(url)
                              .status_code(StatusCode::NOT_FOUND);
                          load.intercept(response).finish();
@@ -2176,6 +3662,32 @@ impl servo::EventLoopWaker for LotusWaker {
 // INTERNAL APP HANDLER (Winit 0.30)
 // ------------------------------------------------------------------
 
+type NodeCallback = ThreadsafeFunction<(String, Vec<napi::bindgen_prelude::Buffer>), ErrorStrategy::Fatal>;
+
+/// Encode a JSON event and deliver it to Node. A free function so it can be used
+/// while `LotusApp::windows` is borrowed.
+fn send_event(callback: &NodeCallback, client_id: &str, payload: &serde_json::Value) {
+    if let Ok(msg) = rmp_serde::encode::to_vec(payload) {
+        let mut wrapped = Vec::with_capacity(msg.len() + 1);
+        wrapped.push(MSG_TYPE_DATA);
+        wrapped.extend(msg);
+        callback.call((client_id.to_string(), vec![wrapped.into()]), ThreadsafeFunctionCallMode::NonBlocking);
+    }
+}
+
+/// pane-blurred for the pane that lost focus (if any), then pane-focused.
+fn send_focus_events(callback: &NodeCallback, window_id: &str, blurred: Option<String>, focused: &str) {
+    let Some(blurred) = blurred else { return };
+    send_event(callback, &format!("{}:{}", window_id, blurred), &serde_json::json!({
+        "event": "pane-blurred",
+        "window_id": window_id
+    }));
+    send_event(callback, &format!("{}:{}", window_id, focused), &serde_json::json!({
+        "event": "pane-focused",
+        "window_id": window_id
+    }));
+}
+
 static WEBVIEW_COUNT: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
 
 struct LotusApp {
@@ -2185,6 +3697,8 @@ struct LotusApp {
     proxy: EventLoopProxy<EngineCommand>,
     callback: ThreadsafeFunction<(String, Vec<napi::bindgen_prelude::Buffer>), ErrorStrategy::Fatal>,
     pending_wake: Arc<AtomicBool>,
+    trays: HashMap<String, tray::TrayHandle>,
+    global_shortcuts: global_shortcut::GlobalShortcuts,
 }
 
 impl Drop for LotusApp {
@@ -2192,6 +3706,9 @@ impl Drop for LotusApp {
         info!("Rust: [Teardown] LotusApp dropping, clearing windows first");
         // Drop all WebViews before shutting down Servo to avoid hangs
         self.windows.clear();
+        popup::discard(None);
+        permission::discard(None);
+        auth::discard(None);
         info!("Rust: [Teardown] Windows cleared, shutting down Servo");
         self.servo = None;
     }
@@ -2209,10 +3726,66 @@ impl LotusApp {
             proxy,
             callback,
             pending_wake: Arc::new(AtomicBool::new(false)),
+            trays: HashMap::new(),
+            global_shortcuts: global_shortcut::GlobalShortcuts::default(),
         };
         app.ensure_servo();
         app
     }
+
+    /// Ids of windows created with `parent` set to `window_id`.
+    fn child_windows(&self, window_id: &str) -> Vec<String> {
+        self.windows.iter()
+            .filter(|(_, w)| w.parent_id.as_deref() == Some(window_id))
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    /// The open modal child blocking input to `window_id`, if any.
+    fn modal_child_of(&self, window_id: &str) -> Option<String> {
+        self.windows.iter()
+            .find(|(_, w)| w.modal && w.parent_id.as_deref() == Some(window_id))
+            .map(|(id, _)| id.clone())
+    }
+
+    /// Exit once the last window and the last tray are gone. Tray-only apps
+    /// keep running in the background.
+    fn exit_if_idle(&self, event_loop: &ActiveEventLoop) {
+        if self.windows.is_empty() && self.trays.is_empty() {
+            info!("Rust: No windows or trays left, exiting event loop");
+            event_loop.exit();
+        }
+    }
+
+    /// Re-enumerate monitors, store the snapshot in APP_STATE and emit
+    /// display-added / display-removed / display-changed for anything that moved.
+    /// winit has no hotplug event, so this runs on resume and whenever the
+    /// platform reports a display change (or on a timer where it can't).
+    fn refresh_displays(&mut self, event_loop: &ActiveEventLoop) {
+        let current = display::enumerate(event_loop);
+        let previous = match APP_STATE.get().and_then(|state| state.lock().ok()) {
+            Some(mut s) => std::mem::replace(&mut s.displays, current.clone()),
+            None => return,
+        };
+
+        // The first snapshot is the baseline, not a hotplug.
+        if previous.is_empty() {
+            return;
+        }
+
+        for change in display::diff(&previous, &current) {
+            let (event_name, info) = match change {
+                display::DisplayChange::Added(d) => ("display-added", d),
+                display::DisplayChange::Removed(d) => ("display-removed", d),
+                display::DisplayChange::Changed(d) => ("display-changed", d),
+            };
+            info!("Rust: {} {}", event_name, info.id);
+            send_event(&self.callback, "global", &serde_json::json!({
+                "event": event_name,
+                "display": info
+            }));
+        }
+    }
     
     fn ensure_servo(&mut self) -> &servo::Servo {
         if self.servo.is_none() {
@@ -2235,9 +3808,10 @@ impl LotusApp {
 }
 
 impl ApplicationHandler<EngineCommand> for LotusApp {
-    fn resumed(&mut self, _event_loop: &ActiveEventLoop) {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
         // App is ready to create windows
         info!("Rust: Application Resumed");
+        self.refresh_displays(event_loop);
     }
 
     fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
@@ -2268,7 +3842,62 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
             }
         }
 
-        event_loop.set_control_flow(ControlFlow::Wait);
+        // Replies read during a grab can leave key events queued on the X11
+        // connection where the socket watcher won't see them.
+        for shortcut in self.global_shortcuts.drain() {
+            send_event(&self.callback, "global", &shortcut.to_json());
+        }
+
+        // Click-through windows with forward_hover get no pointer events from the OS,
+        // so synthesize CursorMoved from the global cursor position.
+        let mut hover_moves = Vec::new();
+        let mut forwarding = false;
+        for instance in self.windows.values_mut() {
+            if !instance.forward_hover {
+                continue;
+            }
+            // Where the cursor can't be read (Wayland, macOS) there's nothing to poll for.
+            let Some(cursor) = platform::cursor_position(&instance.window) else {
+                continue;
+            };
+            let Ok(origin) = instance.window.inner_position() else {
+                continue;
+            };
+            forwarding = true;
+            let size = instance.window.inner_size();
+            let local = winit::dpi::PhysicalPosition::new((cursor.x - origin.x) as f64, (cursor.y - origin.y) as f64);
+            let inside = local.x >= 0.0 && local.y >= 0.0 && local.x < size.width as f64 && local.y < size.height as f64;
+            if inside && instance.last_forwarded_cursor != Some(local) {
+                instance.last_forwarded_cursor = Some(local);
+                hover_moves.push((instance.window.id(), local));
+            }
+        }
+        for (winit_id, position) in hover_moves {
+            // SAFETY: the dummy id is only compared against other ids, never dereferenced.
+            let device_id = unsafe { winit::event::DeviceId::dummy() };
+            self.window_event(event_loop, winit_id, WindowEvent::CursorMoved { device_id, position });
+        }
+
+        // Keys whose before-input budget ran out go to the page unanswered.
+        let mut input_deadline: Option<Instant> = None;
+        for (window_id, instance) in self.windows.iter_mut() {
+            if instance.before_input.is_empty() {
+                continue;
+            }
+            for (blurred, focused) in instance.release_input(false) {
+                send_focus_events(&self.callback, window_id, blurred, &focused);
+            }
+            if let Some(deadline) = instance.before_input.next_deadline() {
+                input_deadline = Some(input_deadline.map_or(deadline, |d| d.min(deadline)));
+            }
+        }
+
+        let hover_deadline = forwarding.then(|| Instant::now() + std::time::Duration::from_millis(16));
+        match (hover_deadline, input_deadline) {
+            (Some(a), Some(b)) => event_loop.set_control_flow(ControlFlow::WaitUntil(a.min(b))),
+            (Some(deadline), None) | (None, Some(deadline)) => event_loop.set_control_flow(ControlFlow::WaitUntil(deadline)),
+            (None, None) => event_loop.set_control_flow(ControlFlow::Wait),
+        }
     }
 
     fn user_event(&mut self, event_loop: &ActiveEventLoop, event: EngineCommand) {
@@ -2324,6 +3953,13 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                     .with_transparent(options.transparent)
                     .with_theme(theme);
 
+                if let Some(path) = &options.icon {
+                    match icon::load_png(path, options.root.as_deref().map(std::path::Path::new)) {
+                        Ok(icon) => window_attrs = window_attrs.with_window_icon(Some(icon)),
+                        Err(e) => warn!("Rust: Window icon not applied: {}", e),
+                    }
+                }
+
                 // NOTE: We intentionally do NOT set no_redirection_bitmap here.
                 // WS_EX_NOREDIRECTIONBITMAP requires a DirectComposition visual tree to
                 // display content. ANGLE/surfman create a regular DXGI swap chain for the
@@ -2340,18 +3976,74 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                     window_attrs = WindowAttributesExtWayland::with_name(window_attrs, class.clone(), class.clone());
                 }
 
+                let mut start_fullscreen = options.fullscreen;
+                let mut initial_size = WinitPhysicalSize::new(options.width, options.height);
+                let mut restored = false;
                 if options.restore_state {
+                    // Saved rects are validated against the live monitor set, so make
+                    // sure it's current (a display may have been unplugged since).
+                    self.refresh_displays(event_loop);
                     if let Some(state) = APP_STATE.get() {
                         if let Ok(s) = state.lock() {
-                            if let Some(ws) = s.window_states.get_window_state(&window_id) {
+                            if let Some(ws) = s.window_states.get_window_state(&window_id, &s.displays) {
+                                initial_size = WinitPhysicalSize::new(ws.width, ws.height);
                                 window_attrs = window_attrs
-                                    .with_inner_size(WinitPhysicalSize::new(ws.width, ws.height))
+                                    .with_inner_size(initial_size)
                                     .with_position(winit::dpi::PhysicalPosition::new(ws.x, ws.y));
+                                start_fullscreen = start_fullscreen || ws.fullscreen;
+                                restored = true;
                             }
                         }
                     }
                 }
 
+                // Owned windows are centered on their owner unless a saved or explicit
+                // position applies. Stacking/minimizing with the owner is left to the OS.
+                let owner = options.parent.as_ref().and_then(|id| self.windows.get(id)).map(|o| o.window.clone());
+                if let (Some(parent_id), None) = (&options.parent, &owner) {
+                    warn!("Rust: Parent window {} not found, creating {} as a top-level window", parent_id, window_id);
+                }
+                if let Some(owner) = &owner {
+                    window_attrs = platform::with_owner(window_attrs, owner);
+                    if !restored && options.display.is_none() {
+                        if let Ok(owner_pos) = owner.outer_position() {
+                            let owner_size = owner.outer_size();
+                            window_attrs = window_attrs.with_position(winit::dpi::PhysicalPosition::new(
+                                owner_pos.x + (owner_size.width as i32 - initial_size.width as i32) / 2,
+                                owner_pos.y + (owner_size.height as i32 - initial_size.height as i32) / 2,
+                            ));
+                        }
+                    }
+                }
+
+                // Explicit display placement wins over restored state; plain `center`
+                // only applies to windows that have no saved position.
+                if options.display.is_some() || (options.center.unwrap_or(false) && !restored && owner.is_none()) {
+                    if options.display.is_some() {
+                        self.refresh_displays(event_loop);
+                    }
+                    let target = APP_STATE.get()
+                        .and_then(|state| state.lock().ok())
+                        .and_then(|s| {
+                            match &options.display {
+                                Some(id) => s.displays.iter().find(|d| &d.id == id).cloned(),
+                                None => s.displays.iter().find(|d| d.is_primary).or(s.displays.first()).cloned(),
+                            }
+                        });
+                    match target {
+                        Some(d) => {
+                            let pos = if options.center.unwrap_or(true) {
+                                display::centered_position(&d, initial_size.width, initial_size.height)
+                            } else {
+                                winit::dpi::PhysicalPosition::new(d.work_area.x, d.work_area.y)
+                            };
+                            info!("Rust: Placing window {} on display {} at {:?}", window_id, d.id, pos);
+                            window_attrs = window_attrs.with_position(pos);
+                        },
+                        None => warn!("Rust: Display {:?} not found, using default placement", options.display),
+                    }
+                }
+
                 let window = match event_loop.create_window(window_attrs) {
                     Ok(w) => Arc::new(w),
                     Err(e) => {
@@ -2361,6 +4053,11 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                 };
 
                 let winit_id = window.id();
+                let modal = owner.is_some() && options.modal.unwrap_or(false);
+                if let Some(owner) = &owner {
+                    // Window is still unmapped here, which is what X11 WMs expect.
+                    platform::set_transient_for(&window, owner, modal);
+                }
                 
                 let display_handle = match window.display_handle() {
                     Ok(h) => h,
@@ -2442,6 +4139,7 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                         pane_id: "main".to_string(),
                         proxy: self.proxy.clone(),
                         ready_to_repaint: ready_to_repaint.clone(),
+                        popup_host: popup::PopupHost { servo: servo.clone(), rendering_context: rendering_context.clone() },
                     });
                     
                     let main_ucm = Rc::new(UserContentManager::new(&servo));
@@ -2449,6 +4147,7 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                     main_ucm.add_script(Rc::new(UserScript::from(IPC_BOOTSTRAP_BASE)));
                     let main_port_script = format!("window.lotus.port = {}; window.lotus.token = '{}'; window.lotus.id = '{}'; window.lotus.paneId = 'main';", port, token, window_id);
                     main_ucm.add_script(Rc::new(UserScript::from(main_port_script.as_str())));
+                    main_ucm.add_script(Rc::new(UserScript::from(pane_key_script(&window_id, "main").as_str())));
                     
                     let theme_str = match mode { dark_light::Mode::Dark => "dark", _ => "light" };
                     let theme_script = format!(r#"
@@ -2457,6 +4156,8 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                     "#, theme_str);
                     main_ucm.add_script(Rc::new(UserScript::from(theme_script.as_str())));
                     main_ucm.add_script(Rc::new(UserScript::from(DRAG_REGION_SCRIPT)));
+                    main_ucm.add_script(Rc::new(UserScript::from(WINDOW_OPEN_SCRIPT)));
+                    main_ucm.add_script(Rc::new(UserScript::from(NOTIFICATION_SCRIPT)));
                     
                     let mut main_builder = WebViewBuilder::new(&servo, main_offscreen.clone())
                         .delegate(main_delegate)
@@ -2502,6 +4203,7 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                         frames_until_stable: 0,
                         ghost_tex: None,
                         ghost_tex_size: None,
+                        history: (Vec::new(), 0),
                     };
                     panes.insert("main".to_string(), main_pane);
                     primary_pane_id = "main".to_string();
@@ -2529,6 +4231,7 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                             pane_id: pane_opt.id.clone(),
                             proxy: self.proxy.clone(),
                             ready_to_repaint: p_ready_to_repaint.clone(),
+                            popup_host: popup::PopupHost { servo: servo.clone(), rendering_context: rendering_context.clone() },
                         });
                         
                         let p_ucm = Rc::new(UserContentManager::new(&servo));
@@ -2536,6 +4239,7 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                         p_ucm.add_script(Rc::new(UserScript::from(IPC_BOOTSTRAP_BASE)));
                         let p_port_script = format!("window.lotus.port = {}; window.lotus.token = '{}'; window.lotus.id = '{}'; window.lotus.paneId = '{}';", port, token, window_id, pane_opt.id);
                         p_ucm.add_script(Rc::new(UserScript::from(p_port_script.as_str())));
+                        p_ucm.add_script(Rc::new(UserScript::from(pane_key_script(&window_id, &pane_opt.id).as_str())));
                         
                         let theme_str = match mode { dark_light::Mode::Dark => "dark", _ => "light" };
                         let theme_script = format!(r#"
@@ -2544,6 +4248,8 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                         "#, theme_str);
                         p_ucm.add_script(Rc::new(UserScript::from(theme_script.as_str())));
                         p_ucm.add_script(Rc::new(UserScript::from(DRAG_REGION_SCRIPT)));
+                        p_ucm.add_script(Rc::new(UserScript::from(WINDOW_OPEN_SCRIPT)));
+                        p_ucm.add_script(Rc::new(UserScript::from(NOTIFICATION_SCRIPT)));
                         
                         let mut p_builder = WebViewBuilder::new(&servo, p_offscreen.clone())
                             .delegate(p_delegate)
@@ -2588,6 +4294,7 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                             frames_until_stable: 0,
                         ghost_tex: None,
                         ghost_tex_size: None,
+                        history: (Vec::new(), 0),
                             };
                         panes.insert(pane_opt.id.clone(), pane);
                     }
@@ -2638,6 +4345,7 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                     comp_vbo: None,
                     u_comp_size_loc: None,
                     u_comp_radius_loc: None,
+                    u_comp_opacity_loc: None,
                     comp_tex_size: None,
                     scene_fbo: None,
                     scene_tex: None,
@@ -2645,6 +4353,27 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                     committed_layout: None,
                     active_window_size: winit::dpi::PhysicalSize::new(size.width, size.height),
                     committed_window_size: None,
+                    fullscreen_mode: FullscreenMode::None,
+                    windowed_bounds: None,
+                    parent_id: owner.as_ref().and(options.parent.clone()),
+                    modal,
+                    opacity: 1.0,
+                    ignore_mouse_events: false,
+                    forward_hover: false,
+                    last_forwarded_cursor: None,
+                    menu_bar: None,
+                    open_dialogs: 0,
+                    deferred_close: None,
+                    winit_modifiers: winit::keyboard::ModifiersState::empty(),
+                    consumed_keys: HashSet::new(),
+                    accelerators: HashMap::new(),
+                    before_input: input::BeforeInput::default(),
+                    ime_composing: false,
+                    ime_preedit_cleared: false,
+                    touch_panes: HashMap::new(),
+                    focus_order: Vec::new(),
+                    pending_navigations: HashMap::new(),
+                    next_navigation_seq: 1,
                 };
 
                 instance.init_stencil_program();
@@ -2652,6 +4381,12 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
 
                 instance.recalculate_layout(winit::dpi::PhysicalSize::new(size.width, size.height), hidpi_scale_factor_val);
 
+                // Entered after creation (rather than via with_fullscreen) so the
+                // windowed bounds are captured for the next state save.
+                if start_fullscreen {
+                    instance.set_fullscreen_mode(FullscreenMode::Borderless);
+                }
+
                 self.windows.insert(window_id.clone(), instance);
                 self.winit_id_to_uuid.insert(winit_id, window_id.clone());
 
@@ -2660,6 +4395,19 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                         s.window_metadata.insert(window_id.clone(), WindowMetadata {
                             root_path: options.root.clone().map(PathBuf::from),
                             last_window_size: Some(size),
+                            persist_zoom: options.persist_zoom.unwrap_or(false),
+                            allowed_origins: options.allowed_origins.clone(),
+                            pane_origins: options.panes.iter()
+                                .filter_map(|p| Some((p.id.clone(), p.allowed_origins.clone()?)))
+                                .collect(),
+                            crash_policy: parse_crash_policy(options.crash_policy.as_ref()).unwrap_or_default(),
+                            pane_crash_policies: options.panes.iter()
+                                .filter_map(|p| Some((p.id.clone(), parse_crash_policy(p.crash_policy.as_ref())?)))
+                                .collect(),
+                            crash_reloads: HashMap::new(),
+                            error_page: options.error_page.as_deref().map(|page| {
+                                page.trim_start_matches("lotus-resource://localhost").trim_start_matches("lotus-resource://").trim_start_matches('/').to_string()
+                            }),
                         });
 
                     }
@@ -2707,6 +4455,50 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                 });
                 event_loop.exit();
             },
+            EngineCommand::RefreshDisplays => {
+                self.refresh_displays(event_loop);
+            },
+            EngineCommand::Tray(tray_id, command) => {
+                match command {
+                    tray::TrayCommand::Create(icon, tooltip) => {
+                        match tray::TrayHandle::new(&tray_id, icon, tooltip) {
+                            Ok(handle) => {
+                                info!("Rust: Tray {} created", tray_id);
+                                self.trays.insert(tray_id, handle);
+                            },
+                            Err(e) => {
+                                warn!("Rust: Tray {} not created: {}", tray_id, e);
+                                send_event(&self.callback, "global", &serde_json::json!({
+                                    "event": "tray-error",
+                                    "tray_id": tray_id,
+                                    "message": e
+                                }));
+                            }
+                        }
+                    },
+                    tray::TrayCommand::SetIcon(icon) => {
+                        if let Some(handle) = self.trays.get(&tray_id) { handle.set_icon(icon); }
+                    },
+                    tray::TrayCommand::SetTooltip(tooltip) => {
+                        if let Some(handle) = self.trays.get(&tray_id) { handle.set_tooltip(tooltip); }
+                    },
+                    tray::TrayCommand::SetMenu(items) => {
+                        if let Some(handle) = self.trays.get(&tray_id) { handle.set_menu(items); }
+                    },
+                    tray::TrayCommand::Destroy => {
+                        self.trays.remove(&tray_id);
+                        self.exit_if_idle(event_loop);
+                    },
+                }
+            },
+            EngineCommand::TrayEvent(tray_id, event) => {
+                if let tray::TrayEvent::Error(e) = &event {
+                    warn!("Rust: Tray {} not created: {}", tray_id, e);
+                    self.trays.remove(&tray_id);
+                }
+                send_event(&self.callback, "global", &event.to_json(&tray_id));
+                self.exit_if_idle(event_loop);
+            },
             EngineCommand::IpcMessage(window_id, raw_bytes) => {
                 // For singular messages (mostly from internal Rust sources),
                 // we ensure the MSG_TYPE_DATA header is present so JS doesn't have to guess.
@@ -2717,7 +4509,7 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                 };
 
                 if msg_type == MSG_TYPE_CONTROL {
-                    intercept_drag_regions(payload, window_id.clone());
+                    intercept_control_messages(payload, window_id.clone());
                 }
 
                 if !raw_bytes.is_empty() && (raw_bytes[0] == MSG_TYPE_CONTROL || raw_bytes[0] == MSG_TYPE_DATA || raw_bytes[0] == 0x03) {
@@ -2743,7 +4535,7 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                     info!("Rust: Processing IPC message from {}. Type: 0x{:02x}, Payload len: {}", window_id, msg_type, payload.len());
 
                     if msg_type == MSG_TYPE_CONTROL {
-                        intercept_drag_regions(payload, window_id.clone());
+                        intercept_control_messages(payload, window_id.clone());
                     }
 
                     // Forward the raw bytes (including the 1-byte header) to Node.js.
@@ -2799,12 +4591,21 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                 }
             },
             EngineCommand::CloseWindow(window_id) => {
+                // Dialogs still point at the native window; it goes once they end.
+                if let Some(instance) = self.windows.get_mut(&window_id).filter(|w| w.open_dialogs > 0) {
+                    instance.deferred_close.get_or_insert(DeferredClose::Command);
+                    return;
+                }
                 self.windows.remove(&window_id);
+                popup::discard(Some(&window_id));
+                permission::discard(Some(&window_id));
+                auth::discard(Some(&window_id));
                 // Clean up per-window metadata so long-running apps don't leak.
                 if let Some(state) = APP_STATE.get() {
                     if let Ok(mut s) = state.lock() {
                         s.window_metadata.remove(&window_id);
                         s.window_start_times.remove(&window_id);
+                        s.pane_keys.retain(|_, (w, _)| *w != window_id);
                     }
                 }
                 // Drop any buffered outgoing frames for this window.
@@ -2853,11 +4654,21 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                 if let Some(instance) = self.windows.get(&window_id) {
                     instance.window.set_minimized(true);
                 }
+                for child in self.child_windows(&window_id) {
+                    if let Some(instance) = self.windows.get(&child) {
+                        instance.window.set_minimized(true);
+                    }
+                }
             },
             EngineCommand::UnminimizeWindow(window_id) => {
                 if let Some(instance) = self.windows.get(&window_id) {
                     instance.window.set_minimized(false);
                 }
+                for child in self.child_windows(&window_id) {
+                    if let Some(instance) = self.windows.get(&child) {
+                        instance.window.set_minimized(false);
+                    }
+                }
             },
             EngineCommand::MaximizeWindow(window_id) => {
                 if let Some(instance) = self.windows.get(&window_id) {
@@ -2971,6 +4782,380 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                     instance.window.set_max_inner_size(size);
                 }
             },
+            EngineCommand::SetFullscreen(window_id, mode) => {
+                let changed = match self.windows.get_mut(&window_id) {
+                    Some(instance) => instance.set_fullscreen_mode(mode),
+                    None => false,
+                };
+                if changed {
+                    info!("Rust: Window {} fullscreen mode -> {}", window_id, mode.as_str());
+                    send_event(&self.callback, &window_id, &serde_json::json!({
+                        "event": "fullscreen-changed",
+                        "window_id": window_id,
+                        "fullscreen": mode != FullscreenMode::None,
+                        "mode": mode.as_str()
+                    }));
+                }
+            },
+            EngineCommand::SetIgnoreMouseEvents(window_id, ignore, forward_hover) => {
+                if let Some(instance) = self.windows.get_mut(&window_id) {
+                    if let Err(e) = instance.window.set_cursor_hittest(!ignore) {
+                        // Panes still stop receiving clicks below; they just won't reach
+                        // whatever is behind the window.
+                        warn!("Rust: set_cursor_hittest unsupported for window {}: {}", window_id, e);
+                    }
+                    if ignore && forward_hover && platform::cursor_position(&instance.window).is_none() {
+                        warn!("Rust: forward_hover unsupported for window {}: the cursor position can't be read", window_id);
+                    }
+                    instance.ignore_mouse_events = ignore;
+                    instance.forward_hover = ignore && forward_hover;
+                    instance.last_forwarded_cursor = None;
+                }
+            },
+            EngineCommand::SetOpacity(window_id, opacity) => {
+                if let Some(instance) = self.windows.get_mut(&window_id) {
+                    let opacity = opacity.clamp(0.0, 1.0);
+                    // Transparent windows fade in the final blit. An opaque surface has
+                    // no alpha to fade (it would just darken), so those need the
+                    // window manager.
+                    if instance.transparent {
+                        instance.opacity = opacity;
+                        instance.window.request_redraw();
+                    } else if !platform::set_window_opacity(&instance.window, opacity) {
+                        warn!("Rust: setOpacity needs `transparent: true` for window {} on this platform", window_id);
+                    }
+                }
+            },
+            EngineCommand::SetIcon(window_id, icon) => {
+                if let Some(instance) = self.windows.get(&window_id) {
+                    instance.window.set_window_icon(icon);
+                }
+            },
+            EngineCommand::SetMenu(window_id, menu) => {
+                if let Some(instance) = self.windows.get_mut(&window_id) {
+                    if let Some(old) = instance.menu_bar.take() {
+                        old.detach(&instance.window);
+                    }
+                    instance.menu_bar = menu.map(|(menu_id, items)| menu::MenuBar::attach(&instance.window, &window_id, menu_id, items));
+                }
+            },
+            EngineCommand::PopupMenu(window_id, pane_id, menu_id, items, position) => {
+                if let Some(instance) = self.windows.get(&window_id) {
+                    let scale_factor = instance.window.scale_factor() as f32;
+                    // No position: open where the pointer last was.
+                    let point = match (position, instance.panes.get(&pane_id)) {
+                        (Some((x, y)), Some(pane)) => {
+                            let (px, py) = pane.physical_origin(scale_factor);
+                            (px + x as f32 * scale_factor, py + y as f32 * scale_factor)
+                        },
+                        _ => (instance.last_mouse_pos.x, instance.last_mouse_pos.y),
+                    };
+                    let point = winit::dpi::PhysicalPosition::new(point.0.round() as i32, point.1.round() as i32);
+                    if let Err(e) = menu::popup(&instance.window, &window_id, &pane_id, &menu_id, items, point) {
+                        warn!("Rust: Context menu {} not shown: {}", menu_id, e);
+                    }
+                }
+            },
+            EngineCommand::MenuActivated(window_id, mut activation) => {
+                if let Some(instance) = self.windows.get_mut(&window_id) {
+                    if activation.pane_id.is_none() {
+                        if let Some(bar) = instance.menu_bar.as_mut().filter(|b| b.menu_id == activation.menu_id) {
+                            activation.checked = bar.activated(&activation.item_id, activation.checked).or(activation.checked);
+                        }
+                        // Menu bar items act on whichever pane has focus.
+                        activation.pane_id = Some(instance.active_pane_id.clone());
+                    }
+                    let client_id = format!("{}:{}", window_id, activation.pane_id.as_deref().unwrap_or("main"));
+                    send_event(&self.callback, &client_id, &activation.to_json(&window_id));
+                }
+            },
+            EngineCommand::BeginDialog(window_id, reply) => {
+                // A window waiting to close doesn't take new dialogs.
+                let parent = self.windows.get_mut(&window_id).filter(|w| w.deferred_close.is_none()).and_then(|instance| {
+                    let parent = dialog::DialogParent::of(&instance.window)?;
+                    instance.open_dialogs += 1;
+                    Some(parent)
+                });
+                if reply.send(parent).is_err() && parent.is_some() {
+                    // Nobody is waiting to run the dialog, so nobody will end it.
+                    if let Some(instance) = self.windows.get_mut(&window_id) {
+                        instance.open_dialogs -= 1;
+                    }
+                }
+            },
+            EngineCommand::EndDialog(window_id) => {
+                let Some(instance) = self.windows.get_mut(&window_id) else { return };
+                instance.open_dialogs = instance.open_dialogs.saturating_sub(1);
+                if instance.open_dialogs > 0 {
+                    return;
+                }
+                match instance.deferred_close.take() {
+                    Some(DeferredClose::Requested) => {
+                        let winit_id = instance.window.id();
+                        self.window_event(event_loop, winit_id, WindowEvent::CloseRequested);
+                    },
+                    Some(DeferredClose::Command) => {
+                        let _ = self.proxy.send_event(EngineCommand::CloseWindow(window_id));
+                    },
+                    None => instance.window.focus_window(),
+                }
+            },
+            EngineCommand::RegisterGlobalShortcut(accelerator, reply) => {
+                self.global_shortcuts.register(event_loop, accelerator, reply);
+            },
+            EngineCommand::UnregisterGlobalShortcut(accelerator, reply) => {
+                let _ = reply.send(self.global_shortcuts.unregister(&accelerator));
+            },
+            EngineCommand::GlobalShortcutsReady => {
+                for shortcut in self.global_shortcuts.ready() {
+                    send_event(&self.callback, "global", &shortcut.to_json());
+                }
+            },
+            EngineCommand::GlobalShortcut(shortcut) => {
+                send_event(&self.callback, "global", &shortcut.to_json());
+            },
+            EngineCommand::RegisterAccelerator(window_id, parsed, accelerator) => {
+                if let Some(instance) = self.windows.get_mut(&window_id) {
+                    instance.accelerators.insert(parsed, accelerator);
+                }
+            },
+            EngineCommand::UnregisterAccelerator(window_id, parsed) => {
+                if let Some(instance) = self.windows.get_mut(&window_id) {
+                    instance.accelerators.remove(&parsed);
+                }
+            },
+            EngineCommand::SetBeforeInput(window_id, enabled, budget_ms) => {
+                if let Some(instance) = self.windows.get_mut(&window_id) {
+                    instance.before_input.enabled = enabled;
+                    if let Some(ms) = budget_ms {
+                        instance.before_input.budget = std::time::Duration::from_millis(ms as u64);
+                    }
+                    if !enabled {
+                        for (blurred, focused) in instance.release_input(true) {
+                            send_focus_events(&self.callback, &window_id, blurred, &focused);
+                        }
+                    }
+                }
+            },
+            EngineCommand::NavigationBlocked(window_id, pane_id, request) => {
+                // Without a window the request drops here, which denies it.
+                if let Some(instance) = self.windows.get_mut(&window_id) {
+                    let seq = instance.next_navigation_seq;
+                    instance.next_navigation_seq = seq.wrapping_add(1);
+                    let url = request.url();
+                    instance.pending_navigations.insert(seq, (pane_id.clone(), request));
+                    send_event(&self.callback, &format!("{}:{}", window_id, pane_id), &serde_json::json!({
+                        "event": "will-navigate",
+                        "window_id": window_id,
+                        "seq": seq,
+                        "url": url
+                    }));
+                }
+            },
+            EngineCommand::ResolveNavigation(window_id, seq, action) => {
+                let pending = self.windows.get_mut(&window_id).and_then(|w| w.pending_navigations.remove(&seq));
+                if let Some((_, request)) = pending {
+                    // Anything but "allow" lets the request drop, which denies it.
+                    match action.as_str() {
+                        "allow" => request.allow(),
+                        "external" => {
+                            if let Err(e) = navigation::open_external(&request.url()) {
+                                warn!("Rust: {}", e);
+                            }
+                        },
+                        _ => {},
+                    }
+                }
+            },
+            EngineCommand::ResolveLogin(window_id, seq, credentials, remember) => {
+                // Dropping the request unanswered cancels the login.
+                let Some(pending) = auth::take(&window_id, seq) else { return };
+                let Some((username, password)) = credentials else { return };
+                if remember {
+                    if let Some(mut s) = APP_STATE.get().and_then(|state| state.lock().ok()) {
+                        s.credentials.insert(pending.key, &pending.url, &username, &password);
+                    }
+                }
+                pending.request.authenticate(username, password);
+            },
+            EngineCommand::ResolvePermission(window_id, seq, allow, remember) => {
+                if let Some(pending) = permission::take(&window_id, seq) {
+                    if remember && pending.can_remember && !pending.origin.is_empty() {
+                        if let Some(mut s) = APP_STATE.get().and_then(|state| state.lock().ok()) {
+                            s.permissions.set(&pending.origin, &pending.permission, Some(allow));
+                        }
+                    }
+                    pending.answer(allow);
+                }
+            },
+            EngineCommand::LoadFailed(window_id, pane_id, failure) => {
+                warn!("Rust: {} failed to load in {} pane {}: {} ({})", failure.url, window_id, pane_id, failure.code, failure.description);
+                send_event(&self.callback, &format!("{}:{}", window_id, pane_id), &failure.to_json(&window_id));
+
+                // Swap a failed document for the error page, unless the error page itself failed.
+                let error_page = APP_STATE.get()
+                    .and_then(|state| state.lock().ok())
+                    .and_then(|s| s.window_metadata.get(&window_id).and_then(|m| m.error_page.clone()));
+                let Some(error_page) = error_page.filter(|_| failure.main_frame) else { return };
+                let failed_path = url::Url::parse(&failure.url).ok()
+                    .filter(|u| u.scheme() == "lotus-resource")
+                    .map(|u| u.path().trim_start_matches('/').to_string());
+                if failed_path.as_deref() == Some(error_page.as_str()) {
+                    return;
+                }
+                let pane = self.windows.get(&window_id).and_then(|w| w.panes.get(&pane_id));
+                if let (Some(pane), Some(url)) = (pane, navigation::error_page_url(&error_page, &failure)) {
+                    pane.webview.load(url);
+                }
+            },
+            EngineCommand::SetAllowedOrigins(window_id, pane_id, origins) => {
+                let Some(mut s) = APP_STATE.get().and_then(|state| state.lock().ok()) else { return };
+                let Some(meta) = s.window_metadata.get_mut(&window_id) else { return };
+                match (pane_id, origins) {
+                    (Some(pane_id), Some(origins)) => { meta.pane_origins.insert(pane_id, origins); },
+                    (Some(pane_id), None) => { meta.pane_origins.remove(&pane_id); },
+                    (None, origins) => meta.allowed_origins = origins,
+                }
+            },
+            EngineCommand::SetCrashPolicy(window_id, pane_id, policy) => {
+                let Some(mut s) = APP_STATE.get().and_then(|state| state.lock().ok()) else { return };
+                let Some(meta) = s.window_metadata.get_mut(&window_id) else { return };
+                match pane_id {
+                    Some(pane_id) => { meta.pane_crash_policies.insert(pane_id, policy); },
+                    None => meta.crash_policy = policy,
+                }
+            },
+            EngineCommand::ResolvePopup(window_id, seq, adopt) => {
+                // Dropping a popup closes its webview and denies its first navigation.
+                let Some(popup) = popup::take(&window_id, seq) else { return };
+                let Some((rect, z_index)) = adopt else { return };
+                let Some(instance) = self.windows.get_mut(&window_id) else { return };
+                let pane_id = popup::pane_id(seq);
+                let popup::Popup { webview, offscreen_ctx, ready_to_repaint, navigation, .. } = popup;
+                let servo_size = ServoPhysicalSize::new(1, 1);
+                instance.panes.insert(pane_id.clone(), PaneInstance {
+                    id: pane_id.clone(),
+                    webview,
+                    rect,
+                    last_notified_rect: None,
+                    last_physical_rect: euclid::default::Rect::new(
+                        euclid::default::Point2D::new(0, 0),
+                        euclid::default::Size2D::new(1, 1)
+                    ),
+                    z_index,
+                    anchor: PaneAnchor::None,
+                    dock_order: 0,
+                    animating: false,
+                    is_dirty: true,
+                    needs_repaint: false,
+                    is_visible: false,
+                    drag_regions: Vec::new(),
+                    no_drag_regions: Vec::new(),
+                    offscreen_ctx,
+                    servo_busy: false,
+                    pending_servo_size: None,
+                    requested_servo_size: servo_size,
+                    current_servo_size: servo_size,
+                    ready_to_repaint,
+                    ready_frame_size: None,
+                    first_frame_painted: false,
+                    first_frame_painted_time: None,
+                    pending_physical_rect: None,
+                    is_resizing: false,
+                    pending_visible: true,
+                    frames_until_stable: 0,
+                    ghost_tex: None,
+                    ghost_tex_size: None,
+                    history: (Vec::new(), 0),
+                });
+                if let Some(navigation) = navigation {
+                    // The popup now loads as one of this window's panes, so the
+                    // window's allowlist applies like any other navigation.
+                    let allowed = APP_STATE.get()
+                        .and_then(|state| state.lock().ok())
+                        .map(|s| match s.window_metadata.get(&window_id).and_then(|m| m.allowed_origins(&pane_id)) {
+                            Some(origins) => navigation.origin_allowed(origins),
+                            None => true,
+                        })
+                        .unwrap_or(true);
+                    if allowed {
+                        navigation.allow();
+                    } else {
+                        info!("Rust: Navigation to {} held for {} pane {}", navigation.url(), window_id, pane_id);
+                        let _ = self.proxy.send_event(EngineCommand::NavigationBlocked(window_id.clone(), pane_id.clone(), navigation));
+                    }
+                }
+                // Size it through the normal resize path.
+                let _ = self.proxy.send_event(EngineCommand::SetPaneRect(window_id, pane_id, rect));
+            },
+            EngineCommand::ResolveInput(window_id, seq, prevent) => {
+                if let Some(instance) = self.windows.get_mut(&window_id) {
+                    instance.before_input.resolve(seq, prevent);
+                    for (blurred, focused) in instance.release_input(false) {
+                        send_focus_events(&self.callback, &window_id, blurred, &focused);
+                    }
+                }
+            },
+            EngineCommand::ShowIme(window_id, pane_id, caret) => {
+                if let Some(instance) = self.windows.get(&window_id) {
+                    // A pane that lost focus meanwhile doesn't get the IME.
+                    if let Some(pane) = instance.panes.get(&pane_id).filter(|_| instance.active_pane_id == pane_id) {
+                        let (x, y) = pane.physical_origin(instance.window.scale_factor() as f32);
+                        instance.window.set_ime_allowed(true);
+                        // Candidate windows open below the caret.
+                        instance.window.set_ime_cursor_area(
+                            winit::dpi::PhysicalPosition::new(x as i32 + caret.min.x, y as i32 + caret.min.y),
+                            winit::dpi::PhysicalSize::new(caret.width().max(1) as u32, caret.height().max(1) as u32),
+                        );
+                    }
+                }
+            },
+            EngineCommand::HideIme(window_id, pane_id) => {
+                if let Some(instance) = self.windows.get_mut(&window_id) {
+                    if instance.active_pane_id == pane_id {
+                        instance.window.set_ime_allowed(false);
+                        instance.dismiss_ime();
+                    }
+                }
+            },
+            EngineCommand::NotificationEvent(notification_id, source, event) => {
+                let payload = event.to_json(&notification_id);
+                match source {
+                    notification::NotificationSource::Node => send_event(&self.callback, "global", &payload),
+                    notification::NotificationSource::Page { window_id, pane_id, title, tag } => {
+                        if let Some(instance) = self.windows.get(&window_id) {
+                            // Clicking a page's notification brings its window back.
+                            if matches!(event, notification::NotificationEvent::Click) {
+                                instance.window.set_minimized(false);
+                                instance.window.set_visible(true);
+                                instance.window.focus_window();
+                            }
+                            // Fire the page's onclick/onclose/onerror. Actions only reach
+                            // service workers, which non-persistent notifications don't have.
+                            let dom_event = match event {
+                                notification::NotificationEvent::Click => Some("click"),
+                                notification::NotificationEvent::Close(_) => Some("close"),
+                                notification::NotificationEvent::Error(_) => Some("error"),
+                                notification::NotificationEvent::Action(_) => None,
+                            };
+                            if let (Some(dom_event), Some(pane)) = (dom_event, instance.panes.get(&pane_id)) {
+                                let script = format!(
+                                    "window.lotus && window.lotus._notificationEvent && window.lotus._notificationEvent({}, {}, '{}')",
+                                    serde_json::Value::String(title),
+                                    serde_json::Value::String(tag),
+                                    dom_event
+                                );
+                                pane.webview.evaluate_javascript(&script, |_| {});
+                            }
+                            send_event(&self.callback, &format!("{}:{}", window_id, pane_id), &payload);
+                        }
+                    }
+                }
+            },
+            EngineCommand::QueryWindow(window_id, reply) => {
+                // Receiver may already be gone if the JS side stopped awaiting; that's fine.
+                let _ = reply.send(self.windows.get(&window_id).map(|instance| instance.snapshot()));
+            },
             EngineCommand::CreatePane(window_id, pane_id, url, rect, z_index, anchor, dock_order) => {
                 let servo = self.ensure_servo().clone();
                 if let Some(instance) = self.windows.get_mut(&window_id) {
@@ -2985,7 +5170,8 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                         pane_id: pane_id.clone(),
                         proxy: self.proxy.clone(),
                         ready_to_repaint: ready_to_repaint.clone(),
-                    });                    
+                        popup_host: popup::PopupHost { servo: servo.clone(), rendering_context: instance.rendering_context.clone() },
+                    });
                     let hidpi_scale_factor_val = instance.window.scale_factor() as f32;
                     let hidpi_scale_factor = Scale::<f32, DeviceIndependentPixel, DevicePixel>::new(hidpi_scale_factor_val);
                     
@@ -3006,7 +5192,10 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
 
                     let port_script = format!("window.lotus.port = {}; window.lotus.token = '{}'; window.lotus.id = '{}'; window.lotus.paneId = '{}';", port, token, window_id, pane_id);
                     user_content_manager.add_script(Rc::new(UserScript::from(port_script.as_str())));
+                    user_content_manager.add_script(Rc::new(UserScript::from(pane_key_script(&window_id, &pane_id).as_str())));
                     user_content_manager.add_script(Rc::new(UserScript::from(DRAG_REGION_SCRIPT)));
+                    user_content_manager.add_script(Rc::new(UserScript::from(WINDOW_OPEN_SCRIPT)));
+                    user_content_manager.add_script(Rc::new(UserScript::from(NOTIFICATION_SCRIPT)));
 
                     let servo_size = ServoPhysicalSize::new(
                         (rect.size.width * hidpi_scale_factor_val).round() as u32,
@@ -3072,6 +5261,7 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                         frames_until_stable: 0,
                         ghost_tex: None,
                         ghost_tex_size: None,
+                        history: (Vec::new(), 0),
                     };
                     instance.panes.insert(pane_id, pane);
                 }
@@ -3079,9 +5269,20 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
             EngineCommand::RemovePane(window_id, pane_id) => {
                 if let Some(instance) = self.windows.get_mut(&window_id) {
                     let _ = instance.panes.remove(&pane_id);
+                    instance.pending_navigations.retain(|_, (p, _)| *p != pane_id);
+                    if let Some(mut s) = APP_STATE.get().and_then(|state| state.lock().ok()) {
+                        s.pane_keys.retain(|_, (w, p)| !(*w == window_id && *p == pane_id));
+                        if let Some(meta) = s.window_metadata.get_mut(&window_id) {
+                            meta.pane_origins.remove(&pane_id);
+                            meta.pane_crash_policies.remove(&pane_id);
+                            meta.crash_reloads.remove(&pane_id);
+                        }
+                    }
                     // Focus fallback: If the removed pane was active, move focus to primary.
                     if instance.active_pane_id == pane_id {
-                        instance.active_pane_id = instance.primary_pane_id.clone();
+                        let primary = instance.primary_pane_id.clone();
+                        let blurred = instance.focus_pane(primary.clone());
+                        send_focus_events(&self.callback, &window_id, blurred, &primary);
                     }
                 }
             },
@@ -3152,7 +5353,81 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
             },
             EngineCommand::FocusPane(window_id, pane_id) => {
                 if let Some(instance) = self.windows.get_mut(&window_id) {
-                    instance.active_pane_id = pane_id;
+                    let blurred = instance.focus_pane(pane_id.clone());
+                    send_focus_events(&self.callback, &window_id, blurred, &pane_id);
+                }
+            },
+            EngineCommand::Navigate(window_id, pane_id, action) => {
+                if let Some(pane) = self.windows.get(&window_id).and_then(|w| w.panes.get(&pane_id)) {
+                    match action {
+                        Navigation::Back => { pane.webview.go_back(1); },
+                        Navigation::Forward => { pane.webview.go_forward(1); },
+                        Navigation::Reload => pane.webview.reload(),
+                        // Servo has no embedder-side stop; the page-level one aborts the load.
+                        Navigation::Stop => pane.webview.evaluate_javascript("window.stop()", |_| {}),
+                    }
+                }
+            },
+            EngineCommand::HistoryChanged(window_id, pane_id, entries, current) => {
+                if let Some(pane) = self.windows.get_mut(&window_id).and_then(|w| w.panes.get_mut(&pane_id)) {
+                    let payload = serde_json::json!({
+                        "event": "history-changed",
+                        "window_id": window_id,
+                        "entries": entries,
+                        "index": current,
+                        "can_go_back": current > 0,
+                        "can_go_forward": current + 1 < entries.len()
+                    });
+                    pane.history = (entries, current);
+                    send_event(&self.callback, &format!("{}:{}", window_id, pane_id), &payload);
+                }
+            },
+            EngineCommand::SetPaneZoom(window_id, pane_id, zoom) => {
+                if let Some(pane) = self.windows.get(&window_id).and_then(|w| w.panes.get(&pane_id)) {
+                    pane.webview.set_page_zoom(zoom.clamp(MIN_ZOOM, MAX_ZOOM));
+                    let zoom = pane.webview.page_zoom();
+                    if let (Some(url), Some(state)) = (pane.webview.url(), APP_STATE.get()) {
+                        if let Ok(mut s) = state.lock() {
+                            if s.window_metadata.get(&window_id).is_some_and(|m| m.persist_zoom) {
+                                s.zoom_levels.set(&url_origin(url.as_str()), zoom);
+                            }
+                        }
+                    }
+                    send_event(&self.callback, &format!("{}:{}", window_id, pane_id), &serde_json::json!({
+                        "event": "zoom-changed",
+                        "window_id": window_id,
+                        "zoom": zoom
+                    }));
+                }
+            },
+            EngineCommand::RestorePaneZoom(window_id, pane_id) => {
+                if let Some(pane) = self.windows.get(&window_id).and_then(|w| w.panes.get(&pane_id)) {
+                    let saved = APP_STATE.get()
+                        .and_then(|state| state.lock().ok())
+                        .filter(|s| s.window_metadata.get(&window_id).is_some_and(|m| m.persist_zoom))
+                        .and_then(|s| pane.webview.url().map(|url| s.zoom_levels.get(&url_origin(url.as_str())).unwrap_or(1.0)));
+                    // A new origin gets its own level (or the default), not the previous page's.
+                    if let Some(zoom) = saved.filter(|z| (z - pane.webview.page_zoom()).abs() > 0.001) {
+                        pane.webview.set_page_zoom(zoom);
+                        send_event(&self.callback, &format!("{}:{}", window_id, pane_id), &serde_json::json!({
+                            "event": "zoom-changed",
+                            "window_id": window_id,
+                            "zoom": zoom
+                        }));
+                    }
+                }
+            },
+            EngineCommand::CyclePaneFocus(window_id, backwards) => {
+                if let Some(instance) = self.windows.get_mut(&window_id) {
+                    if let Some(pane_id) = instance.next_focus_pane(backwards) {
+                        let blurred = instance.focus_pane(pane_id.clone());
+                        send_focus_events(&self.callback, &window_id, blurred, &pane_id);
+                    }
+                }
+            },
+            EngineCommand::SetFocusOrder(window_id, order) => {
+                if let Some(instance) = self.windows.get_mut(&window_id) {
+                    instance.focus_order = order;
                 }
             },
         }
@@ -3202,7 +5477,39 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
         event_loop.set_control_flow(ControlFlow::Wait);
 
         if let Some(uuid) = self.winit_id_to_uuid.get(&window_id).cloned() {
+            // While a modal child is open the owner's panes get no pointer or keyboard
+            // input. A click on the owner brings the modal back to the front instead.
+            let is_input = matches!(event,
+                WindowEvent::CursorMoved { .. } | WindowEvent::MouseInput { .. } |
+                WindowEvent::MouseWheel { .. } | WindowEvent::KeyboardInput { .. } |
+                WindowEvent::Ime(_) | WindowEvent::Touch(_) |
+                WindowEvent::PinchGesture { .. } | WindowEvent::RotationGesture { .. });
+            if is_input {
+                if let Some(modal_id) = self.modal_child_of(&uuid) {
+                    if let WindowEvent::MouseInput { state: winit::event::ElementState::Pressed, .. } = event {
+                        if let Some(modal) = self.windows.get(&modal_id) {
+                            modal.window.focus_window();
+                            platform::request_attention(&modal.window);
+                        }
+                    }
+                    return;
+                }
+                // Same for a native dialog parented to this window.
+                if self.windows.get(&uuid).is_some_and(|w| w.open_dialogs > 0) {
+                    return;
+                }
+            }
+
             if let Some(instance) = self.windows.get_mut(&uuid) {
+                // Click-through also covers platforms without hittest support: pane
+                // clicks and scrolls are dropped, hover only survives with forward_hover.
+                if instance.ignore_mouse_events {
+                    match event {
+                        WindowEvent::MouseInput { .. } | WindowEvent::MouseWheel { .. } => return,
+                        WindowEvent::CursorMoved { .. } if !instance.forward_hover => return,
+                        _ => {}
+                    }
+                }
                 match event {
                     WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
                         info!("Rust: Scale factor changed to {}", scale_factor);
@@ -3211,6 +5518,8 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                         // but no longer calls webview.resize() itself, so we must do it here.
                         let sf = scale_factor as f32;
                         for pane in instance.panes.values_mut() {
+                            // Only the device scale changes; page zoom is applied on top by Servo.
+                            pane.webview.set_hidpi_scale_factor(Scale::new(sf));
                             let new_pw = ((pane.rect.size.width  * sf).round() as u32).max(1);
                             let new_ph = ((pane.rect.size.height * sf).round() as u32).max(1);
                             if true { // Always valid now since we clamped to >= 1
@@ -3227,23 +5536,38 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                     },
                     WindowEvent::CloseRequested => {
                         info!("Rust: Window close requested");
+                        // Dialogs still point at the native window; it goes once they end.
+                        if instance.open_dialogs > 0 {
+                            instance.deferred_close.get_or_insert(DeferredClose::Requested);
+                            return;
+                        }
                         
                         // Save window state before closing
                         if let Some(state) = APP_STATE.get() {
                             if let Ok(mut s) = state.lock() {
-                                let position = instance.window.outer_position().ok();
-                                let size = instance.window.inner_size();
+                                // While fullscreen, persist the windowed bounds so the
+                                // window doesn't come back sized to the whole monitor.
+                                let (position, size) = match instance.windowed_bounds {
+                                    Some((pos, size)) => (Some(pos), size),
+                                    None => (instance.window.outer_position().ok(), instance.window.inner_size()),
+                                };
                                 let window_state = window_state::WindowState {
                                     x: position.map(|p| p.x).unwrap_or(0),
                                     y: position.map(|p| p.y).unwrap_or(0),
                                     width: size.width,
                                     height: size.height,
                                     maximized: instance.window.is_maximized(),
-                                    fullscreen: false, // Default for now
+                                    fullscreen: instance.window.fullscreen().is_some(),
+                                    display_id: instance.window.current_monitor().map(|m| display::display_id(&m)),
+                                    scale_factor: Some(instance.window.scale_factor()),
                                 };
                                 s.window_states.save_window_state(&uuid, window_state);
+                                s.zoom_levels.flush();
                             }
                         }
+                        if let Some(bar) = &instance.menu_bar {
+                            bar.detach(&instance.window);
+                        }
                         let mut close_msg = Vec::new();
                         if rmp_serde::encode::write(&mut close_msg, &serde_json::json!({"event": "window-closed", "window_id": uuid})).is_ok() {
                             let mut wrapped = Vec::with_capacity(close_msg.len() + 1);
@@ -3253,22 +5577,31 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                         }
                         self.windows.remove(&uuid);
                         self.winit_id_to_uuid.remove(&window_id);
+                        popup::discard(Some(&uuid));
+                        permission::discard(Some(&uuid));
+                        auth::discard(Some(&uuid));
                         info!("Rust: Window '{}' closed. {} windows remaining", uuid, self.windows.len());
 
+                        // Owned windows don't outlive their owner.
+                        for child in self.child_windows(&uuid) {
+                            let child_winit_id = self.windows.get(&child).map(|c| c.window.id());
+                            if let Some(child_winit_id) = child_winit_id {
+                                self.window_event(event_loop, child_winit_id, WindowEvent::CloseRequested);
+                            }
+                        }
+
                         // Clean up per-window metadata to avoid unbounded growth
                         // across many open/close cycles.
                         if let Some(state) = APP_STATE.get() {
                             if let Ok(mut s) = state.lock() {
                                 s.window_metadata.remove(&uuid);
                                 s.window_start_times.remove(&uuid);
+                                s.pane_keys.retain(|_, (w, _)| *w != uuid);
                             }
                         }
                         // Drop any buffered outgoing frames for this window.
                         if let Some(p) = WS_PENDING.get() { p.remove(&uuid); }
-                        if self.windows.is_empty() {
-                            info!("Rust: Last window closed, exiting event loop");
-                            event_loop.exit();
-                        }
+                        self.exit_if_idle(event_loop);
                     },
                     WindowEvent::RedrawRequested => {
                         let _ = instance.rendering_context.make_current();
@@ -3516,6 +5849,7 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                                                 instance.gl.uniform_2_f32(instance.u_comp_size_loc.as_ref(), active_w as f32, active_h as f32);
                                                 let radius = instance.corner_radius.unwrap_or(0.0) as f32 * scale_factor;
                                                 instance.gl.uniform_1_f32(instance.u_comp_radius_loc.as_ref(), radius);
+                                                instance.gl.uniform_1_f32(instance.u_comp_opacity_loc.as_ref(), 1.0);
 
                                                 instance.gl.draw_arrays(glow::TRIANGLE_STRIP, 0, 4);
                                                 instance.gl.bind_vertex_array(None);
@@ -3560,6 +5894,7 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                                                 instance.gl.uniform_2_f32(instance.u_comp_size_loc.as_ref(), active_w as f32, active_h as f32);
                                                 let radius = instance.corner_radius.unwrap_or(0.0) as f32 * scale_factor;
                                                 instance.gl.uniform_1_f32(instance.u_comp_radius_loc.as_ref(), radius);
+                                                instance.gl.uniform_1_f32(instance.u_comp_opacity_loc.as_ref(), 1.0);
                                                 instance.gl.draw_arrays(glow::TRIANGLE_STRIP, 0, 4);
                                                 instance.gl.bind_vertex_array(None);
                                             }
@@ -3593,6 +5928,8 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                                 
                                 instance.gl.uniform_2_f32(instance.u_comp_size_loc.as_ref(), active_w as f32, active_h as f32);
                                 instance.gl.uniform_1_f32(instance.u_comp_radius_loc.as_ref(), 0.0);
+                                // Window opacity is applied once, here, so overlapping panes fade as one.
+                                instance.gl.uniform_1_f32(instance.u_comp_opacity_loc.as_ref(), instance.opacity);
 
                                 instance.gl.draw_arrays(glow::TRIANGLE_STRIP, 0, 4);
                                 instance.gl.bind_vertex_array(None);
@@ -3883,6 +6220,7 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                             found_id
                         };
 
+                        let mut focus_to = None;
                         if let Some(pane_id) = target_pane_id {
                             if let Some(pane) = instance.panes.get_mut(&pane_id) {
                                     // Unified physical coordinate translation
@@ -3972,7 +6310,7 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
 
                                 // Focus on click
                                 if is_pressed {
-                                    instance.active_pane_id = pane.id.clone();
+                                    focus_to = Some(pane.id.clone());
                                 }
 
                                 let translated_point = Point2D::new(instance.last_mouse_pos.x - px, instance.last_mouse_pos.y - py);
@@ -3983,6 +6321,10 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                                 )));
                             }
                         }
+                        if let Some(pane_id) = focus_to {
+                            let blurred = instance.focus_pane(pane_id.clone());
+                            send_focus_events(&self.callback, &uuid, blurred, &pane_id);
+                        }
                     },
                     WindowEvent::MouseWheel { delta, .. } => {
                         let scroll_multiplier = 20.0;
@@ -4049,32 +6391,77 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                             }
                         }
                     },
+                    WindowEvent::Touch(touch) => {
+                        // Servo's touch events carry no force, so pen pressure and
+                        // tilt (touch.force) can't be passed on yet.
+                        let point = Point2D::new(touch.location.x as f32, touch.location.y as f32);
+                        let event_type = match touch.phase {
+                            winit::event::TouchPhase::Started => TouchEventType::Down,
+                            winit::event::TouchPhase::Moved => TouchEventType::Move,
+                            winit::event::TouchPhase::Ended => TouchEventType::Up,
+                            winit::event::TouchPhase::Cancelled => TouchEventType::Cancel,
+                        };
+                        let pane_id = if touch.phase == winit::event::TouchPhase::Started {
+                            let hit = instance.pane_at(point).map(|pane| pane.id.clone());
+                            if let Some(pane_id) = &hit {
+                                instance.touch_panes.insert(touch.id, pane_id.clone());
+                                let blurred = instance.focus_pane(pane_id.clone());
+                                send_focus_events(&self.callback, &uuid, blurred, pane_id);
+                            }
+                            hit
+                        } else if matches!(touch.phase, winit::event::TouchPhase::Moved) {
+                            instance.touch_panes.get(&touch.id).cloned()
+                        } else {
+                            instance.touch_panes.remove(&touch.id)
+                        };
+                        let scale_factor = instance.window.scale_factor() as f32;
+                        if let Some(pane) = pane_id.and_then(|id| instance.panes.get(&id)) {
+                            let origin = pane.physical_rect(scale_factor).origin;
+                            let translated_point = Point2D::new(point.x - origin.x, point.y - origin.y);
+                            pane.webview.notify_input_event(InputEvent::Touch(TouchEvent::new(
+                                event_type,
+                                TouchId(touch.id as i32),
+                                servo::WebViewPoint::Device(translated_point)
+                            )));
+                        }
+                    },
+                    WindowEvent::PinchGesture { delta, .. } => {
+                        // Trackpad pinch zooms the pane under the pointer, around the pointer.
+                        let scale_factor = instance.window.scale_factor() as f32;
+                        if let Some(pane) = instance.pane_at(instance.last_mouse_pos) {
+                            let origin = pane.physical_rect(scale_factor).origin;
+                            let center = Point2D::new(instance.last_mouse_pos.x - origin.x, instance.last_mouse_pos.y - origin.y);
+                            pane.webview.pinch_zoom(1.0 + delta as f32, center);
+                        }
+                    },
+                    WindowEvent::RotationGesture { delta, .. } => {
+                        // The web has no standard rotate event; report it so Node can act on it.
+                        if let Some(pane) = instance.pane_at(instance.last_mouse_pos) {
+                            send_event(&self.callback, &format!("{}:{}", uuid, pane.id), &serde_json::json!({
+                                "event": "rotate-gesture",
+                                "window_id": uuid,
+                                "degrees": delta
+                            }));
+                        }
+                    },
                     WindowEvent::Moved(position) => {
-                        let mut msg = Vec::new();
-                        if rmp_serde::encode::write(&mut msg, &serde_json::json!({
+                        send_event(&self.callback, &uuid, &serde_json::json!({
                             "event": "moved",
                             "window_id": uuid,
                             "x": position.x,
                             "y": position.y
-                        })).is_ok() {
-                            let mut wrapped = Vec::with_capacity(msg.len() + 1);
-                            wrapped.push(MSG_TYPE_DATA);
-                            wrapped.extend(msg);
-                            self.callback.call((uuid.clone(), vec![wrapped.into()]), ThreadsafeFunctionCallMode::NonBlocking);
-                        }
+                        }));
                     },
                     WindowEvent::Focused(focused) => {
+                        // Releases of keys held while focus left go elsewhere.
+                        if !focused {
+                            instance.consumed_keys.clear();
+                        }
                         let event_name = if focused { "focused" } else { "unfocused" };
-                        let mut msg = Vec::new();
-                        if rmp_serde::encode::write(&mut msg, &serde_json::json!({
+                        send_event(&self.callback, &uuid, &serde_json::json!({
                             "event": event_name,
                             "window_id": uuid
-                        })).is_ok() {
-                            let mut wrapped = Vec::with_capacity(msg.len() + 1);
-                            wrapped.push(MSG_TYPE_DATA);
-                            wrapped.extend(msg);
-                            self.callback.call((uuid.clone(), vec![wrapped.into()]), ThreadsafeFunctionCallMode::NonBlocking);
-                        }
+                        }));
                     },
                     WindowEvent::HoveredFile(path) => {
                         let path_str = path.to_string_lossy().into_owned();
@@ -4155,9 +6542,121 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                         }
                     },
                     WindowEvent::ModifiersChanged(modifiers) => {
+                        instance.winit_modifiers = modifiers.state();
                         instance.modifiers = map_winit_modifiers(modifiers);
                     },
+                    WindowEvent::Ime(ime) => {
+                        // Composition goes to the focused pane: Start on the first
+                        // preedit, Update while it changes, End with the committed text.
+                        let mut events = Vec::new();
+                        match ime {
+                            winit::event::Ime::Enabled => {},
+                            winit::event::Ime::Preedit(text, _) => {
+                                // IMEs send an empty preedit to clear it before a commit,
+                                // and also when the user cancels the composition.
+                                if text.is_empty() {
+                                    if instance.ime_composing {
+                                        instance.ime_preedit_cleared = true;
+                                        events.push(ImeEvent::Composition(CompositionEvent { state: CompositionState::Update, data: text }));
+                                    }
+                                } else {
+                                    // A new preedit instead of a commit: the last composition was cancelled.
+                                    if std::mem::take(&mut instance.ime_preedit_cleared) && instance.ime_composing {
+                                        instance.ime_composing = false;
+                                        events.push(ImeEvent::Composition(CompositionEvent { state: CompositionState::End, data: String::new() }));
+                                    }
+                                    if !instance.ime_composing {
+                                        instance.ime_composing = true;
+                                        events.push(ImeEvent::Composition(CompositionEvent { state: CompositionState::Start, data: String::new() }));
+                                    }
+                                    events.push(ImeEvent::Composition(CompositionEvent { state: CompositionState::Update, data: text }));
+                                }
+                            },
+                            winit::event::Ime::Commit(text) => {
+                                if !instance.ime_composing {
+                                    events.push(ImeEvent::Composition(CompositionEvent { state: CompositionState::Start, data: String::new() }));
+                                }
+                                instance.ime_composing = false;
+                                instance.ime_preedit_cleared = false;
+                                events.push(ImeEvent::Composition(CompositionEvent { state: CompositionState::End, data: text }));
+                            },
+                            winit::event::Ime::Disabled => {
+                                instance.ime_preedit_cleared = false;
+                                if instance.ime_composing {
+                                    instance.ime_composing = false;
+                                    events.push(ImeEvent::Dismissed);
+                                }
+                            },
+                        }
+                        if let Some(pane) = instance.panes.get(&instance.active_pane_id) {
+                            for event in events {
+                                pane.webview.notify_input_event(InputEvent::Ime(event));
+                            }
+                        }
+                    },
                     WindowEvent::KeyboardInput { event, .. } => {
+                        // The page never saw the press, so it doesn't get repeats or the release.
+                        if instance.consumed_keys.contains(&event.physical_key) {
+                            if event.state == winit::event::ElementState::Released {
+                                instance.consumed_keys.remove(&event.physical_key);
+                                return;
+                            }
+                            if event.repeat {
+                                return;
+                            }
+                        }
+
+                        // Menu accelerators win over the page, like a native menu bar.
+                        if event.state == winit::event::ElementState::Pressed && !event.repeat {
+                            use winit::platform::modifier_supplement::KeyEventExtModifierSupplement;
+                            let key = event.key_without_modifiers();
+                            let matched = instance.menu_bar.as_mut().and_then(|bar| {
+                                let item_id = bar.match_accelerator(instance.winit_modifiers, &key)?;
+                                let checked = bar.activated(&item_id, None);
+                                Some(menu::MenuActivation {
+                                    menu_id: bar.menu_id.clone(),
+                                    item_id,
+                                    pane_id: Some(instance.active_pane_id.clone()),
+                                    checked,
+                                })
+                            });
+                            if let Some(activation) = matched {
+                                instance.consumed_keys.insert(event.physical_key);
+                                let client_id = format!("{}:{}", uuid, instance.active_pane_id);
+                                send_event(&self.callback, &client_id, &activation.to_json(&uuid));
+                                return;
+                            }
+                        }
+
+                        // Then the window's own accelerators; held keys repeat silently.
+                        if event.state == winit::event::ElementState::Pressed {
+                            use winit::platform::modifier_supplement::KeyEventExtModifierSupplement;
+                            let key = event.key_without_modifiers();
+                            let matched = instance.accelerators.iter()
+                                .find(|(acc, _)| acc.matches(instance.winit_modifiers, &key))
+                                .map(|(_, accelerator)| accelerator.clone());
+                            if let Some(accelerator) = matched {
+                                instance.consumed_keys.insert(event.physical_key);
+                                if !event.repeat {
+                                    let client_id = format!("{}:{}", uuid, instance.active_pane_id);
+                                    send_event(&self.callback, &client_id, &serde_json::json!({
+                                        "event": "accelerator",
+                                        "window_id": uuid,
+                                        "accelerator": accelerator
+                                    }));
+                                }
+                                return;
+                            }
+                        }
+
+                        // Ctrl+F6 / Ctrl+Shift+F6 move focus between panes, once
+                        // before-input has had its say (see `deliver_key`).
+                        let mods = instance.winit_modifiers;
+                        let traversal = (event.state == winit::event::ElementState::Pressed
+                            && event.logical_key == winit::keyboard::Key::Named(winit::keyboard::NamedKey::F6)
+                            && mods.control_key() && !mods.alt_key() && !mods.super_key())
+                            .then(|| mods.shift_key());
+
                         let state = match event.state {
                             winit::event::ElementState::Pressed => KeyState::Down,
                             winit::event::ElementState::Released => KeyState::Up,
@@ -4165,19 +6664,59 @@ impl ApplicationHandler<EngineCommand> for LotusApp {
                         
                         let key = map_winit_key(&event.logical_key);
                         let code = map_winit_code(event.physical_key);
+                        let input = serde_json::json!({
+                            "type": if state == KeyState::Down { "keyDown" } else { "keyUp" },
+                            "key": key.to_string(),
+                            "code": code.to_string(),
+                            "isAutoRepeat": event.repeat,
+                            "location": map_winit_location(event.location) as u32,
+                            "shift": instance.winit_modifiers.shift_key(),
+                            "control": instance.winit_modifiers.control_key(),
+                            "alt": instance.winit_modifiers.alt_key(),
+                            "meta": instance.winit_modifiers.super_key()
+                        });
                         
                         let keyboard_event = ServoKeyboardEvent::new_without_event(
                             state,
                             key,
                             code,
-                            Location::Standard,
+                            map_winit_location(event.location),
                             instance.modifiers,
                             event.repeat,
-                            false, // is_composing
+                            instance.ime_composing,
                         );
-                        
-                        if let Some(pane) = instance.panes.get(&instance.active_pane_id) {
-                            pane.webview.notify_input_event(InputEvent::Keyboard(keyboard_event));
+                        let pane_id = instance.active_pane_id.clone();
+                        let queued = input::QueuedKey {
+                            pane_id: pane_id.clone(),
+                            event: InputEvent::Keyboard(keyboard_event),
+                            physical: event.physical_key,
+                            pressed: state == KeyState::Down,
+                            traversal,
+                        };
+
+                        // Node gets a look first. Keys queue behind any still waiting,
+                        // so order is kept even once interception is switched off.
+                        if instance.before_input.enabled || !instance.before_input.is_empty() {
+                            if !instance.before_input.enabled {
+                                let mut moves = instance.release_input(true);
+                                moves.extend(instance.deliver_key(queued));
+                                for (blurred, focused) in moves {
+                                    send_focus_events(&self.callback, &uuid, blurred, &focused);
+                                }
+                                return;
+                            }
+                            let seq = instance.before_input.hold(queued);
+                            send_event(&self.callback, &format!("{}:{}", uuid, pane_id), &serde_json::json!({
+                                "event": "before-input",
+                                "window_id": uuid,
+                                "seq": seq,
+                                "input": input
+                            }));
+                            return;
+                        }
+
+                        if let Some((blurred, focused)) = instance.deliver_key(queued) {
+                            send_focus_events(&self.callback, &uuid, blurred, &focused);
                         }
                     },
                     _ => {}
@@ -4235,6 +6774,12 @@ impl App {
             window_start_times: HashMap::new(),
             vfs: None,
             resource_cache: ByteLimitedLruCache::new(128 * 1024 * 1024), // 128MB limit
+            displays: Vec::new(),
+            app_id: app_id.clone(),
+            zoom_levels: window_state::ZoomLevels::new(&app_id),
+            permissions: window_state::PermissionDecisions::new(&app_id),
+            credentials: auth::CredentialCache::default(),
+            pane_keys: HashMap::new(),
         }));
         APP_STATE.set(app_state.clone()).ok();
 
@@ -4269,11 +6814,29 @@ impl App {
                 }
             };
             let proxy = event_loop.create_proxy();
+            #[cfg(target_os = "linux")]
+            platform::set_x11_event_loop(winit::platform::x11::EventLoopExtX11::is_x11(&event_loop));
             
             // Store proxy in global static and signal main thread
             EVENT_LOOP_PROXY.set(proxy.clone()).ok();
             let _ = proxy_tx.send(proxy.clone());
 
+            // winit 0.30 has no monitor hotplug event. X11 reports display and
+            // work area changes itself; elsewhere, poll for them instead.
+            let display_proxy = proxy.clone();
+            let watched = platform::watch_displays(move || display_proxy.send_event(EngineCommand::RefreshDisplays).is_ok());
+            if !watched {
+                let display_proxy = proxy.clone();
+                thread::spawn(move || {
+                    loop {
+                        thread::sleep(std::time::Duration::from_secs(3));
+                        if display_proxy.send_event(EngineCommand::RefreshDisplays).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+
             info!("Rust: Starting Event Loop (LotusApp)");
             let mut lotus_app = LotusApp::new(proxy, event_callback);
             let _ = event_loop.run_app(&mut lotus_app);
@@ -4670,12 +7233,85 @@ impl App {
         Ok(())
     }
 
+    /// Monitors known to the event loop: bounds, work area, scale factor and primary flag.
+    /// Served from the snapshot refreshed on startup and every few seconds thereafter.
+    #[napi]
+    pub fn get_displays(&self) -> Vec<DisplayInfo> {
+        APP_STATE.get()
+            .and_then(|state| state.lock().ok())
+            .map(|s| s.displays.clone())
+            .unwrap_or_default()
+    }
+
+    /// Remembered permission answers: origin -> permission -> granted.
+    #[napi]
+    pub fn get_permission_decisions(&self) -> HashMap<String, HashMap<String, bool>> {
+        APP_STATE.get()
+            .and_then(|state| state.lock().ok())
+            .map(|s| s.permissions.all().clone())
+            .unwrap_or_default()
+    }
+
+    /// Remember (or with None, forget) a permission answer for an origin.
+    #[napi]
+    pub fn set_permission_decision(&self, origin: String, permission: String, granted: Option<bool>) {
+        if let Some(mut s) = APP_STATE.get().and_then(|state| state.lock().ok()) {
+            s.permissions.set(&origin, &permission, granted);
+        }
+    }
+
+    /// Forget remembered permission answers for one origin, or all of them.
+    #[napi]
+    pub fn clear_permission_decisions(&self, origin: Option<String>) {
+        if let Some(mut s) = APP_STATE.get().and_then(|state| state.lock().ok()) {
+            s.permissions.clear(origin.as_deref());
+        }
+    }
+
+    /// Forget logins remembered for the session, for one origin or all.
+    #[napi]
+    pub fn clear_auth_cache(&self, origin: Option<String>) {
+        if let Some(mut s) = APP_STATE.get().and_then(|state| state.lock().ok()) {
+            s.credentials.clear(origin.as_deref());
+        }
+    }
+
+    /// Open an http(s) or mailto URL in the user's default handler.
+    #[napi]
+    pub fn open_external(&self, url: String) -> napi::Result<()> {
+        navigation::open_external(&url).map_err(napi::Error::from_reason)
+    }
+
     #[napi]
     pub fn quit(&self) {
         if let Some(proxy) = EVENT_LOOP_PROXY.get() {
             let _ = proxy.send_event(EngineCommand::Quit);
         }
     }
+
+    /// Grab an accelerator system-wide. Presses and releases arrive as
+    /// "global-shortcut" events even when no Lotus window has focus.
+    /// Rejects if the accelerator is invalid or another application owns it.
+    #[napi]
+    pub async fn register_global_shortcut(&self, accelerator: String) -> napi::Result<()> {
+        global_shortcut_request(|reply| EngineCommand::RegisterGlobalShortcut(accelerator, reply)).await
+    }
+
+    #[napi]
+    pub async fn unregister_global_shortcut(&self, accelerator: String) -> napi::Result<()> {
+        global_shortcut_request(|reply| EngineCommand::UnregisterGlobalShortcut(accelerator, reply)).await
+    }
+}
+
+async fn global_shortcut_request(command: impl FnOnce(global_shortcut::Reply) -> EngineCommand) -> napi::Result<()> {
+    let proxy = EVENT_LOOP_PROXY.get()
+        .ok_or_else(|| napi::Error::from_reason("Event loop is not running"))?;
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    proxy.send_event(command(tx))
+        .map_err(|_| napi::Error::from_reason("Event loop has shut down"))?;
+    rx.await
+        .map_err(|_| napi::Error::from_reason("Event loop dropped the request"))?
+        .map_err(napi::Error::from_reason)
 }
 
 // ------------------------------------------------------------------
@@ -4683,14 +7319,18 @@ impl App {
 // ------------------------------------------------------------------
 
 #[napi]
-pub fn create_window(options: WindowOptions) -> WindowHandle {
+pub fn create_window(options: WindowOptions) -> napi::Result<WindowHandle> {
+    let allowlists = options.allowed_origins.iter().chain(options.panes.iter().filter_map(|p| p.allowed_origins.as_ref()));
+    for origins in allowlists {
+        navigation::check_allowlist(origins).map_err(napi::Error::from_reason)?;
+    }
     let window_id = options.id.clone().unwrap_or_else(|| Uuid::new_v4().to_string());
     
     if let Some(proxy) = EVENT_LOOP_PROXY.get() {
         let _ = proxy.send_event(EngineCommand::CreateWindow(options, window_id.clone()));
     }
     
-    WindowHandle {
+    Ok(WindowHandle {
         id: window_id,
-    }
+    })
 }
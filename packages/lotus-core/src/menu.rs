@@ -0,0 +1,622 @@
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use winit::dpi::PhysicalPosition;
+use winit::keyboard::{Key, ModifiersState};
+use winit::window::Window;
+
+use crate::accelerator::Accelerator;
+
+/// One entry of a native menu, as built by lotus.js from a menu template.
+#[napi(object)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MenuItemOptions {
+    /// Reported back in activation events. Defaults to the label.
+    pub id: Option<String>,
+    pub label: Option<String>,
+    /// "normal" (default), "separator", "checkbox", "radio" or "submenu".
+    #[napi(js_name = "type")]
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+    pub checked: Option<bool>,
+    pub enabled: Option<bool>,
+    pub visible: Option<bool>,
+    pub accelerator: Option<String>,
+    pub submenu: Option<Vec<MenuItemOptions>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuItemKind {
+    Normal,
+    Separator,
+    Checkbox,
+    Radio,
+    Submenu,
+}
+
+impl MenuItemOptions {
+    pub fn kind(&self) -> MenuItemKind {
+        match self.kind.as_deref() {
+            Some("separator") => MenuItemKind::Separator,
+            Some("checkbox") => MenuItemKind::Checkbox,
+            Some("radio") => MenuItemKind::Radio,
+            Some("submenu") => MenuItemKind::Submenu,
+            _ if self.submenu.is_some() => MenuItemKind::Submenu,
+            _ => MenuItemKind::Normal,
+        }
+    }
+
+    pub fn item_id(&self) -> String {
+        self.id.clone().or_else(|| self.label.clone()).unwrap_or_default()
+    }
+
+    pub fn children(&self) -> &[MenuItemOptions] {
+        self.submenu.as_deref().unwrap_or(&[])
+    }
+}
+
+/// Flip a checkbox or select a radio item, given its position in `items`.
+/// Consecutive radio items in the same submenu form one group.
+/// Returns the item's new checked state.
+pub fn toggle(items: &mut [MenuItemOptions], index: usize) -> Option<bool> {
+    match items[index].kind() {
+        MenuItemKind::Checkbox => {
+            let checked = !items[index].checked.unwrap_or(false);
+            items[index].checked = Some(checked);
+            Some(checked)
+        }
+        MenuItemKind::Radio => {
+            let mut start = index;
+            while start > 0 && items[start - 1].kind() == MenuItemKind::Radio {
+                start -= 1;
+            }
+            let mut end = index;
+            while end + 1 < items.len() && items[end + 1].kind() == MenuItemKind::Radio {
+                end += 1;
+            }
+            for (i, item) in items[start..=end].iter_mut().enumerate() {
+                item.checked = Some(start + i == index);
+            }
+            Some(true)
+        }
+        _ => None,
+    }
+}
+
+/// Path (indexes through submenus) of the item with `item_id`.
+fn find_path(items: &[MenuItemOptions], item_id: &str) -> Option<Vec<usize>> {
+    for (i, item) in items.iter().enumerate() {
+        if item.kind() != MenuItemKind::Separator && item.item_id() == item_id {
+            return Some(vec![i]);
+        }
+        if let Some(mut path) = find_path(item.children(), item_id) {
+            path.insert(0, i);
+            return Some(path);
+        }
+    }
+    None
+}
+
+/// The sibling list containing the item at `path`, and its index in that list.
+fn siblings_mut<'a>(items: &'a mut Vec<MenuItemOptions>, path: &[usize]) -> Option<(&'a mut Vec<MenuItemOptions>, usize)> {
+    let (last, parents) = path.split_last()?;
+    let mut siblings = items;
+    for &i in parents {
+        siblings = siblings.get_mut(i)?.submenu.as_mut()?;
+    }
+    (*last < siblings.len()).then_some((siblings, *last))
+}
+
+/// First enabled, activatable item whose accelerator matches the key press.
+fn find_accelerator(items: &[MenuItemOptions], modifiers: ModifiersState, key: &Key) -> Option<String> {
+    for item in items {
+        if item.enabled == Some(false) || item.visible == Some(false) {
+            continue;
+        }
+        match item.kind() {
+            MenuItemKind::Separator => {}
+            MenuItemKind::Submenu => {
+                if let Some(id) = find_accelerator(item.children(), modifiers, key) {
+                    return Some(id);
+                }
+            }
+            _ => {
+                let matched = item.accelerator.as_deref()
+                    .and_then(|a| Accelerator::parse(a).ok())
+                    .is_some_and(|a| a.matches(modifiers, key));
+                if matched {
+                    return Some(item.item_id());
+                }
+            }
+        }
+    }
+    None
+}
+
+#[derive(Debug)]
+pub struct MenuActivation {
+    pub menu_id: String,
+    pub item_id: String,
+    pub pane_id: Option<String>, // set for context menus
+    pub checked: Option<bool>,
+}
+
+impl MenuActivation {
+    pub fn to_json(&self, window_id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "event": "menu-activate",
+            "window_id": window_id,
+            "pane_id": self.pane_id,
+            "menu_id": self.menu_id,
+            "item_id": self.item_id,
+            "checked": self.checked
+        })
+    }
+
+    pub fn send(window_id: &str, activation: MenuActivation) {
+        if let Some(proxy) = crate::EVENT_LOOP_PROXY.get() {
+            let _ = proxy.send_event(crate::EngineCommand::MenuActivated(window_id.to_string(), activation));
+        }
+    }
+}
+
+/// A window's menu bar. Keeps its own copy of the items so accelerators work (and
+/// checkbox/radio state stays right) even where no native menu bar can be shown.
+pub struct MenuBar {
+    pub menu_id: String,
+    items: Vec<MenuItemOptions>,
+    native: Option<backend::NativeMenuBar>,
+}
+
+impl MenuBar {
+    pub fn attach(window: &Window, window_id: &str, menu_id: String, items: Vec<MenuItemOptions>) -> Self {
+        let native = match backend::NativeMenuBar::attach(window, window_id, &menu_id, &items) {
+            Ok(native) => Some(native),
+            Err(e) => {
+                log::warn!("Rust: Native menu bar unavailable for window {} ({}); accelerators still work", window_id, e);
+                None
+            }
+        };
+        MenuBar { menu_id, items, native }
+    }
+
+    pub fn detach(&self, window: &Window) {
+        if let Some(native) = &self.native {
+            native.detach(window);
+        }
+    }
+
+    /// Apply an activation to the local copy. `reported` is the checked state the
+    /// native menu already shows, if it tracks it itself. Returns the new state.
+    pub fn activated(&mut self, item_id: &str, reported: Option<bool>) -> Option<bool> {
+        let path = find_path(&self.items, item_id)?;
+        let (siblings, index) = siblings_mut(&mut self.items, &path)?;
+        let checked = match (siblings[index].kind(), reported) {
+            (MenuItemKind::Checkbox, Some(checked)) => {
+                siblings[index].checked = Some(checked);
+                Some(checked)
+            }
+            _ => toggle(siblings, index),
+        };
+        if checked.is_some() {
+            if let Some(native) = &self.native {
+                native.sync(&self.items);
+            }
+        }
+        checked
+    }
+
+    /// Item id of the accelerator matching this key press, if any.
+    pub fn match_accelerator(&self, modifiers: ModifiersState, key: &Key) -> Option<String> {
+        find_accelerator(&self.items, modifiers, key)
+    }
+}
+
+/// Why no menu bar can be shown here, if it can't. `setMenu` checks this first
+/// so it fails up front instead of leaving a menu nobody sees.
+pub fn bar_support() -> Result<(), String> {
+    backend::bar_support()
+}
+
+/// Whether `setMenu` can show a menu bar. On Linux that takes an X11 session
+/// with a global menu (com.canonical.AppMenu.Registrar, e.g. KDE Plasma).
+#[napi(js_name = "menuBarSupported")]
+pub fn menu_bar_supported() -> bool {
+    bar_support().is_ok()
+}
+
+/// Show `items` as a native context menu at a window-relative physical position.
+pub fn popup(window: &Window, window_id: &str, pane_id: &str, menu_id: &str, items: Vec<MenuItemOptions>, position: PhysicalPosition<i32>) -> Result<(), String> {
+    backend::popup(window, window_id, pane_id, menu_id, items, position)
+}
+
+/// Linux: menu bars are exported over dbusmenu and registered with the global
+/// menu registrar (KDE Plasma, Unity-style panels). There's no in-window
+/// fallback, so without a registrar (or under Wayland) `bar_support` fails.
+/// Context menus are GTK popups driven from a dedicated
+/// GTK thread, since winit windows have no toolkit.
+#[cfg(target_os = "linux")]
+mod backend {
+    use std::cell::RefCell;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use gtk::prelude::*;
+    use once_cell::sync::OnceCell;
+    use winit::dpi::PhysicalPosition;
+    use winit::window::Window;
+    use zbus::zvariant::ObjectPath;
+
+    use super::{MenuActivation, MenuItemKind, MenuItemOptions};
+    use crate::dbusmenu::{self, DbusMenu, MenuModel};
+    use crate::platform;
+
+    static NEXT_MENU: AtomicU32 = AtomicU32::new(1);
+
+    fn session() -> Option<&'static zbus::blocking::Connection> {
+        static CONN: OnceCell<Option<zbus::blocking::Connection>> = OnceCell::new();
+        CONN.get_or_init(|| zbus::blocking::Connection::session().ok()).as_ref()
+    }
+
+    const REGISTRAR: &str = "com.canonical.AppMenu.Registrar";
+    const REGISTRAR_PATH: &str = "/com/canonical/AppMenu/Registrar";
+
+    pub fn bar_support() -> Result<(), String> {
+        if !platform::x11_event_loop() {
+            return Err("menu bars need X11; Wayland has no global menu".to_string());
+        }
+        let conn = session().ok_or("no session bus")?;
+        let has_registrar = conn
+            .call_method(Some("org.freedesktop.DBus"), "/org/freedesktop/DBus", Some("org.freedesktop.DBus"), "NameHasOwner", &(REGISTRAR,))
+            .and_then(|reply| reply.body().deserialize::<bool>())
+            .unwrap_or(false);
+        if !has_registrar {
+            return Err(format!("no global menu on this desktop ({} isn't on the session bus)", REGISTRAR));
+        }
+        Ok(())
+    }
+
+    pub struct NativeMenuBar {
+        path: String,
+        model: Arc<Mutex<MenuModel>>,
+        x11_id: u32,
+    }
+
+    impl NativeMenuBar {
+        pub fn attach(window: &Window, window_id: &str, menu_id: &str, items: &[MenuItemOptions]) -> Result<Self, String> {
+            bar_support()?;
+            let x11_id = platform::x11_window_id(window).ok_or("global menus need X11")?;
+            let conn = session().ok_or("no session bus")?;
+
+            let path = format!("/com/lotus/MenuBar/{}", NEXT_MENU.fetch_add(1, Ordering::Relaxed));
+            let model = Arc::new(Mutex::new(MenuModel::new()));
+            let (window_id, menu_id) = (window_id.to_string(), menu_id.to_string());
+            let on_activate: dbusmenu::ActivateFn = Arc::new(move |item_id, checked| {
+                MenuActivation::send(&window_id, MenuActivation { menu_id: menu_id.clone(), item_id, pane_id: None, checked });
+            });
+            conn.object_server().at(path.as_str(), DbusMenu::new(model.clone(), on_activate))
+                .map_err(|e| e.to_string())?;
+            dbusmenu::set_items(conn, &path, &model, items.to_vec());
+
+            // KDE reads these window properties; other hosts use the registrar.
+            if let Some(name) = conn.unique_name() {
+                platform::set_x11_string_property(window, "_KDE_NET_WM_APPMENU_SERVICE_NAME", name.as_str());
+            }
+            platform::set_x11_string_property(window, "_KDE_NET_WM_APPMENU_OBJECT_PATH", &path);
+            let bar = NativeMenuBar { path, model, x11_id };
+            let object_path = ObjectPath::try_from(bar.path.as_str()).map_err(|e| e.to_string())?;
+            if let Err(e) = conn.call_method(Some(REGISTRAR), REGISTRAR_PATH, Some(REGISTRAR), "RegisterWindow", &(x11_id, object_path)) {
+                bar.detach(window);
+                return Err(format!("the global menu registrar refused the window: {}", e));
+            }
+
+            Ok(bar)
+        }
+
+        pub fn detach(&self, _window: &Window) {
+            if let Some(conn) = session() {
+                let _ = conn.call_method(Some(REGISTRAR), REGISTRAR_PATH, Some(REGISTRAR), "UnregisterWindow", &(self.x11_id,));
+                let _ = conn.object_server().remove::<DbusMenu, _>(self.path.as_str());
+            }
+        }
+
+        pub fn sync(&self, items: &[MenuItemOptions]) {
+            if let Some(conn) = session() {
+                dbusmenu::set_items(conn, &self.path, &self.model, items.to_vec());
+            }
+        }
+    }
+
+    thread_local! {
+        // The open popup must outlive the closure that showed it.
+        static POPUP: RefCell<Option<gtk::Menu>> = const { RefCell::new(None) };
+    }
+
+    fn build(menu: &gtk::Menu, items: &[MenuItemOptions], send: &Arc<dyn Fn(String, Option<bool>) + Send + Sync>) {
+        let mut radio_group: Option<gtk::RadioMenuItem> = None;
+        for item in items {
+            if item.visible == Some(false) {
+                continue;
+            }
+            let label = item.label.clone().unwrap_or_default();
+            let item_id = item.item_id();
+            let kind = item.kind();
+            if kind != MenuItemKind::Radio {
+                radio_group = None;
+            }
+            let widget: gtk::MenuItem = match kind {
+                MenuItemKind::Separator => {
+                    menu.append(&gtk::SeparatorMenuItem::new());
+                    continue;
+                }
+                MenuItemKind::Checkbox => {
+                    let check = gtk::CheckMenuItem::with_label(&label);
+                    check.set_active(item.checked.unwrap_or(false));
+                    let send = send.clone();
+                    check.connect_activate(move |c| send(item_id.clone(), Some(c.is_active())));
+                    check.upcast()
+                }
+                MenuItemKind::Radio => {
+                    let radio = match &radio_group {
+                        Some(group) => gtk::RadioMenuItem::with_label_from_widget(group, Some(&label)),
+                        None => gtk::RadioMenuItem::with_label(&label),
+                    };
+                    radio.set_active(item.checked.unwrap_or(false));
+                    let send = send.clone();
+                    // Fires for the item being deselected too; only report the new pick.
+                    radio.connect_activate(move |r| if r.is_active() { send(item_id.clone(), Some(true)) });
+                    radio_group = Some(radio.clone());
+                    radio.upcast()
+                }
+                MenuItemKind::Submenu => {
+                    let parent = gtk::MenuItem::with_label(&label);
+                    let submenu = gtk::Menu::new();
+                    build(&submenu, item.children(), send);
+                    parent.set_submenu(Some(&submenu));
+                    parent
+                }
+                MenuItemKind::Normal => {
+                    let entry = gtk::MenuItem::with_label(&label);
+                    let send = send.clone();
+                    entry.connect_activate(move |_| send(item_id.clone(), None));
+                    entry
+                }
+            };
+            widget.set_sensitive(item.enabled != Some(false));
+            menu.append(&widget);
+        }
+    }
+
+    pub fn popup(window: &Window, window_id: &str, pane_id: &str, menu_id: &str, items: Vec<MenuItemOptions>, position: PhysicalPosition<i32>) -> Result<(), String> {
+        // Screen coordinates where the platform reports window positions (X11);
+        // otherwise fall back to the pointer, which is where context menus open anyway.
+        let screen_pos = window.inner_position().ok().map(|origin| (origin.x + position.x, origin.y + position.y));
+        let (window_id, pane_id, menu_id) = (window_id.to_string(), pane_id.to_string(), menu_id.to_string());
+        let send: Arc<dyn Fn(String, Option<bool>) + Send + Sync> = Arc::new(move |item_id, checked| {
+            MenuActivation::send(&window_id, MenuActivation {
+                menu_id: menu_id.clone(),
+                item_id,
+                pane_id: Some(pane_id.clone()),
+                checked,
+            });
+        });
+
+        let shown = platform::run_on_gtk_thread(move || {
+            let menu = gtk::Menu::new();
+            build(&menu, &items, &send);
+            menu.show_all();
+            let root = gtk::gdk::Screen::default().and_then(|s| s.root_window());
+            match (screen_pos, root) {
+                (Some((x, y)), Some(root)) => menu.popup_at_rect(
+                    &root,
+                    &gtk::gdk::Rectangle::new(x, y, 1, 1),
+                    gtk::gdk::Gravity::NorthWest,
+                    gtk::gdk::Gravity::NorthWest,
+                    None,
+                ),
+                _ => menu.popup_at_pointer(None),
+            }
+            POPUP.with(|p| *p.borrow_mut() = Some(menu));
+        });
+        if shown { Ok(()) } else { Err("GTK could not be initialized".to_string()) }
+    }
+}
+
+/// Windows and macOS: native menus through muda.
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+mod backend {
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    use muda::{CheckMenuItem, ContextMenu, IsMenuItem, MenuEvent, MenuItem, PredefinedMenuItem, Submenu};
+    use winit::dpi::PhysicalPosition;
+    use winit::raw_window_handle::{HasWindowHandle, RawWindowHandle};
+    use winit::window::Window;
+
+    use super::{MenuActivation, MenuItemKind, MenuItemOptions};
+
+    pub fn bar_support() -> Result<(), String> {
+        Ok(())
+    }
+
+    // muda ids carry everything needed to route an activation:
+    // window, pane (empty for menu bars), menu, item and the popup's post-click check state.
+    const SEP: char = '\u{1f}';
+
+    fn encode_id(window_id: &str, pane_id: &str, menu_id: &str, item_id: &str, checked: Option<bool>) -> String {
+        let checked = match checked { Some(true) => "1", Some(false) => "0", None => "" };
+        [window_id, pane_id, menu_id, item_id, checked].join(&SEP.to_string())
+    }
+
+    fn install_handler() {
+        static ONCE: std::sync::Once = std::sync::Once::new();
+        ONCE.call_once(|| {
+            MenuEvent::set_event_handler(Some(|event: MenuEvent| {
+                let parts: Vec<&str> = event.id().0.split(SEP).collect();
+                let [window_id, pane_id, menu_id, item_id, checked] = parts[..] else { return };
+                MenuActivation::send(window_id, MenuActivation {
+                    menu_id: menu_id.to_string(),
+                    item_id: item_id.to_string(),
+                    pane_id: (!pane_id.is_empty()).then(|| pane_id.to_string()),
+                    checked: match checked { "1" => Some(true), "0" => Some(false), _ => None },
+                });
+            }));
+        });
+    }
+
+    enum Entry {
+        Item(MenuItem),
+        Check(CheckMenuItem),
+        Separator(PredefinedMenuItem),
+        Sub(Submenu),
+    }
+
+    impl Entry {
+        fn as_item(&self) -> &dyn IsMenuItem {
+            match self {
+                Entry::Item(i) => i,
+                Entry::Check(i) => i,
+                Entry::Separator(i) => i,
+                Entry::Sub(i) => i,
+            }
+        }
+    }
+
+    /// Build muda entries. Check items are collected so menu bars can re-sync
+    /// radio groups after an activation. For popups (`is_popup`) the resulting
+    /// check state is baked into the id, since the menu is gone once it fires.
+    fn build(items: &[MenuItemOptions], ids: (&str, &str, &str), is_popup: bool, checks: &mut HashMap<String, CheckMenuItem>) -> Vec<Entry> {
+        let (window_id, pane_id, menu_id) = ids;
+        items.iter().filter(|item| item.visible != Some(false)).map(|item| {
+            let label = item.label.clone().unwrap_or_default();
+            let enabled = item.enabled != Some(false);
+            let accelerator = item.accelerator.as_deref().and_then(|a| muda::accelerator::Accelerator::from_str(a).ok());
+            let kind = item.kind();
+            let after = match kind {
+                MenuItemKind::Checkbox if is_popup => Some(!item.checked.unwrap_or(false)),
+                MenuItemKind::Radio if is_popup => Some(true),
+                _ => None,
+            };
+            let id = encode_id(window_id, pane_id, menu_id, &item.item_id(), after);
+            match kind {
+                MenuItemKind::Separator => Entry::Separator(PredefinedMenuItem::separator()),
+                MenuItemKind::Checkbox | MenuItemKind::Radio => {
+                    let check = CheckMenuItem::with_id(id, label, enabled, item.checked.unwrap_or(false), accelerator);
+                    checks.insert(item.item_id(), check.clone());
+                    Entry::Check(check)
+                }
+                MenuItemKind::Submenu => {
+                    let sub = Submenu::with_id(id, label, enabled);
+                    for child in build(item.children(), ids, is_popup, checks) {
+                        let _ = sub.append(child.as_item());
+                    }
+                    Entry::Sub(sub)
+                }
+                MenuItemKind::Normal => Entry::Item(MenuItem::with_id(id, label, enabled, accelerator)),
+            }
+        }).collect()
+    }
+
+    fn build_menu(items: &[MenuItemOptions], ids: (&str, &str, &str), is_popup: bool, checks: &mut HashMap<String, CheckMenuItem>) -> muda::Menu {
+        let menu = muda::Menu::new();
+        for entry in build(items, ids, is_popup, checks) {
+            let _ = menu.append(entry.as_item());
+        }
+        menu
+    }
+
+    pub struct NativeMenuBar {
+        menu: muda::Menu,
+        checks: HashMap<String, CheckMenuItem>,
+    }
+
+    impl NativeMenuBar {
+        pub fn attach(window: &Window, window_id: &str, menu_id: &str, items: &[MenuItemOptions]) -> Result<Self, String> {
+            install_handler();
+            let mut checks = HashMap::new();
+            let menu = build_menu(items, (window_id, "", menu_id), false, &mut checks);
+            #[cfg(target_os = "windows")]
+            {
+                let Ok(RawWindowHandle::Win32(h)) = window.window_handle().map(|h| h.as_raw()) else {
+                    return Err("no Win32 window handle".to_string());
+                };
+                unsafe { menu.init_for_hwnd(h.hwnd.get()) }.map_err(|e| e.to_string())?;
+            }
+            #[cfg(target_os = "macos")]
+            {
+                // macOS has one app-wide menu bar; the last window to attach owns it.
+                let _ = window;
+                menu.init_for_nsapp();
+            }
+            Ok(NativeMenuBar { menu, checks })
+        }
+
+        pub fn detach(&self, window: &Window) {
+            #[cfg(target_os = "windows")]
+            if let Ok(RawWindowHandle::Win32(h)) = window.window_handle().map(|h| h.as_raw()) {
+                let _ = unsafe { self.menu.remove_for_hwnd(h.hwnd.get()) };
+            }
+            #[cfg(target_os = "macos")]
+            {
+                let _ = window;
+                self.menu.remove_for_nsapp();
+            }
+        }
+
+        pub fn sync(&self, items: &[MenuItemOptions]) {
+            fn walk(items: &[MenuItemOptions], checks: &HashMap<String, CheckMenuItem>) {
+                for item in items {
+                    if let Some(check) = checks.get(&item.item_id()) {
+                        check.set_checked(item.checked.unwrap_or(false));
+                    }
+                    walk(item.children(), checks);
+                }
+            }
+            walk(items, &self.checks);
+        }
+    }
+
+    pub fn popup(window: &Window, window_id: &str, pane_id: &str, menu_id: &str, items: Vec<MenuItemOptions>, position: PhysicalPosition<i32>) -> Result<(), String> {
+        install_handler();
+        let menu = build_menu(&items, (window_id, pane_id, menu_id), true, &mut HashMap::new());
+        let position = muda::dpi::Position::Physical(muda::dpi::PhysicalPosition::new(position.x, position.y));
+        match window.window_handle().map(|h| h.as_raw()) {
+            #[cfg(target_os = "windows")]
+            Ok(RawWindowHandle::Win32(h)) => {
+                unsafe { menu.show_context_menu_for_hwnd(h.hwnd.get(), Some(position)) };
+                Ok(())
+            }
+            #[cfg(target_os = "macos")]
+            Ok(RawWindowHandle::AppKit(h)) => {
+                unsafe { menu.show_context_menu_for_nsview(h.ns_view.as_ptr(), Some(position)) };
+                Ok(())
+            }
+            _ => Err("no native window handle".to_string()),
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+mod backend {
+    use winit::dpi::PhysicalPosition;
+    use winit::window::Window;
+
+    use super::MenuItemOptions;
+
+    pub fn bar_support() -> Result<(), String> {
+        Err("native menus are not supported on this platform".to_string())
+    }
+
+    pub struct NativeMenuBar;
+
+    impl NativeMenuBar {
+        pub fn attach(_window: &Window, _window_id: &str, _menu_id: &str, _items: &[MenuItemOptions]) -> Result<Self, String> {
+            Err("native menus are not supported on this platform".to_string())
+        }
+        pub fn detach(&self, _window: &Window) {}
+        pub fn sync(&self, _items: &[MenuItemOptions]) {}
+    }
+
+    pub fn popup(_window: &Window, _window_id: &str, _pane_id: &str, _menu_id: &str, _items: Vec<MenuItemOptions>, _position: PhysicalPosition<i32>) -> Result<(), String> {
+        Err("native menus are not supported on this platform".to_string())
+    }
+}
@@ -0,0 +1,261 @@
+use napi_derive::napi;
+
+use crate::{EngineCommand, EVENT_LOOP_PROXY};
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct NotificationAction {
+    /// Reported back in the "action" event.
+    pub id: String,
+    pub label: String,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct NotificationOptions {
+    pub title: String,
+    pub body: Option<String>,
+    /// PNG path, resolved like window icons (disk first, then the VFS).
+    pub icon: Option<String>,
+    pub actions: Option<Vec<NotificationAction>>,
+    /// Stay on screen until dismissed.
+    pub require_interaction: Option<bool>,
+    pub silent: Option<bool>,
+}
+
+/// Who asked for a notification, which decides where its events go.
+#[derive(Debug, Clone)]
+pub enum NotificationSource {
+    Node,
+    /// A page called `new Notification()`; events go to the window with the pane id,
+    /// and back to the page's Notification, found by its title and tag.
+    Page { window_id: String, pane_id: String, title: String, tag: String },
+}
+
+#[derive(Debug)]
+pub enum NotificationEvent {
+    Click,
+    Action(String), // action id
+    Close(&'static str), // reason: "expired", "dismissed", "closed" or "unknown"
+    Error(String),
+}
+
+impl NotificationEvent {
+    pub fn to_json(&self, notification_id: &str) -> serde_json::Value {
+        match self {
+            NotificationEvent::Click => serde_json::json!({
+                "event": "notification-click",
+                "notification_id": notification_id
+            }),
+            NotificationEvent::Action(action_id) => serde_json::json!({
+                "event": "notification-action",
+                "notification_id": notification_id,
+                "action_id": action_id
+            }),
+            NotificationEvent::Close(reason) => serde_json::json!({
+                "event": "notification-close",
+                "notification_id": notification_id,
+                "reason": reason
+            }),
+            NotificationEvent::Error(message) => serde_json::json!({
+                "event": "notification-error",
+                "notification_id": notification_id,
+                "message": message
+            }),
+        }
+    }
+}
+
+fn send_event(notification_id: &str, source: &NotificationSource, event: NotificationEvent) {
+    if let Some(proxy) = EVENT_LOOP_PROXY.get() {
+        let _ = proxy.send_event(EngineCommand::NotificationEvent(notification_id.to_string(), source.clone(), event));
+    }
+}
+
+/// Show a notification without blocking the caller (the event loop or Node).
+/// Returns the id that its events will carry.
+pub fn show(source: NotificationSource, options: NotificationOptions, root: Option<std::path::PathBuf>) -> String {
+    let id = uuid::Uuid::new_v4().to_string();
+    let notification_id = id.clone();
+    std::thread::spawn(move || {
+        if let Err(e) = backend::show(&notification_id, &source, &options, root.as_deref()) {
+            log::warn!("Rust: Notification not shown: {}", e);
+            send_event(&notification_id, &source, NotificationEvent::Error(e));
+        }
+    });
+    id
+}
+
+pub fn close(notification_id: &str) {
+    let notification_id = notification_id.to_string();
+    std::thread::spawn(move || backend::close(&notification_id));
+}
+
+/// Show a desktop notification. Events arrive as notification-click,
+/// notification-action, notification-close and notification-error with the returned id.
+#[napi(js_name = "showNotification")]
+pub fn show_notification(options: NotificationOptions) -> String {
+    show(NotificationSource::Node, options, None)
+}
+
+#[napi(js_name = "closeNotification")]
+pub fn close_notification(notification_id: String) {
+    close(&notification_id);
+}
+
+/// org.freedesktop.Notifications on the session bus.
+#[cfg(target_os = "linux")]
+mod backend {
+    use std::collections::HashMap;
+    use std::path::Path;
+    use std::sync::Mutex;
+
+    use once_cell::sync::OnceCell;
+    use zbus::zvariant::Value;
+
+    use super::{send_event, NotificationEvent, NotificationOptions, NotificationSource};
+    use crate::icon;
+
+    const NAME: &str = "org.freedesktop.Notifications";
+    const PATH: &str = "/org/freedesktop/Notifications";
+
+    struct Active {
+        notification_id: String,
+        source: NotificationSource,
+    }
+
+    struct Notifier {
+        conn: zbus::blocking::Connection,
+        // Server-assigned id -> ours.
+        active: Mutex<HashMap<u32, Active>>,
+    }
+
+    impl Notifier {
+        fn take(&self, server_id: u32) -> Option<Active> {
+            self.active.lock().unwrap().remove(&server_id)
+        }
+
+        fn with<R>(&self, server_id: u32, f: impl FnOnce(&Active) -> R) -> Option<R> {
+            self.active.lock().unwrap().get(&server_id).map(f)
+        }
+    }
+
+    fn notifier() -> Result<&'static Notifier, String> {
+        static NOTIFIER: OnceCell<Result<Notifier, String>> = OnceCell::new();
+        NOTIFIER.get_or_init(|| {
+            let conn = zbus::blocking::Connection::session().map_err(|e| format!("No session bus: {}", e))?;
+            listen(conn.clone())?;
+            Ok(Notifier { conn, active: Mutex::new(HashMap::new()) })
+        }).as_ref().map_err(Clone::clone)
+    }
+
+    /// One thread per signal, translating server ids back to our notifications.
+    fn listen(conn: zbus::blocking::Connection) -> Result<(), String> {
+        let proxy = zbus::blocking::Proxy::new(&conn, NAME, PATH, NAME).map_err(|e| e.to_string())?;
+        let actions = proxy.receive_signal("ActionInvoked").map_err(|e| e.to_string())?;
+        let closes = proxy.receive_signal("NotificationClosed").map_err(|e| e.to_string())?;
+
+        std::thread::spawn(move || {
+            for signal in actions {
+                let Ok((server_id, action)) = signal.body().deserialize::<(u32, String)>() else { continue };
+                let Ok(notifier) = notifier() else { continue };
+                let event = if action == "default" { NotificationEvent::Click } else { NotificationEvent::Action(action) };
+                notifier.with(server_id, |a| send_event(&a.notification_id, &a.source, event));
+            }
+        });
+        std::thread::spawn(move || {
+            for signal in closes {
+                let Ok((server_id, reason)) = signal.body().deserialize::<(u32, u32)>() else { continue };
+                let Ok(notifier) = notifier() else { continue };
+                let reason = match reason {
+                    1 => "expired",
+                    2 => "dismissed",
+                    3 => "closed",
+                    _ => "unknown",
+                };
+                if let Some(active) = notifier.take(server_id) {
+                    send_event(&active.notification_id, &active.source, NotificationEvent::Close(reason));
+                }
+            }
+        });
+        Ok(())
+    }
+
+    fn app_name() -> String {
+        crate::APP_STATE.get()
+            .and_then(|state| state.lock().ok())
+            .map(|s| s.app_id.clone())
+            .unwrap_or_else(|| "lotus".to_string())
+    }
+
+    pub fn show(notification_id: &str, source: &NotificationSource, options: &NotificationOptions, root: Option<&Path>) -> Result<(), String> {
+        let notifier = notifier()?;
+
+        let mut hints: HashMap<&str, Value> = HashMap::new();
+        let mut app_icon = String::new();
+        if let Some(path) = &options.icon {
+            // Files on disk can be passed by path; VFS icons are sent as pixels.
+            let on_disk = match root {
+                Some(root) if Path::new(path).is_relative() => root.join(path),
+                _ => Path::new(path).to_path_buf(),
+            };
+            if on_disk.is_file() {
+                app_icon = on_disk.canonicalize().unwrap_or(on_disk).to_string_lossy().into_owned();
+            } else {
+                let (rgba, width, height) = icon::read_image(path, root).and_then(|bytes| icon::decode_png_rgba(&bytes))?;
+                // (iiibiiay): width, height, rowstride, has_alpha, bits, channels, data
+                let image = (width as i32, height as i32, (width * 4) as i32, true, 8i32, 4i32, rgba);
+                hints.insert("image-data", Value::from(image));
+            }
+        }
+        if options.silent == Some(true) {
+            hints.insert("suppress-sound", Value::from(true));
+        }
+
+        // "default" is what servers send for a click on the notification body.
+        let mut actions = vec!["default".to_string(), String::new()];
+        for action in options.actions.iter().flatten() {
+            actions.push(action.id.clone());
+            actions.push(action.label.clone());
+        }
+        let timeout: i32 = if options.require_interaction == Some(true) { 0 } else { -1 };
+
+        let reply = notifier.conn.call_method(
+            Some(NAME),
+            PATH,
+            Some(NAME),
+            "Notify",
+            &(app_name(), 0u32, app_icon, options.title.as_str(), options.body.as_deref().unwrap_or(""), actions, hints, timeout),
+        ).map_err(|e| format!("Notification server unavailable: {}", e))?;
+        let server_id: u32 = reply.body().deserialize().map_err(|e| e.to_string())?;
+
+        notifier.active.lock().unwrap().insert(server_id, Active {
+            notification_id: notification_id.to_string(),
+            source: source.clone(),
+        });
+        Ok(())
+    }
+
+    pub fn close(notification_id: &str) {
+        let Ok(notifier) = notifier() else { return };
+        let server_id = notifier.active.lock().unwrap().iter()
+            .find(|(_, a)| a.notification_id == notification_id)
+            .map(|(id, _)| *id);
+        if let Some(server_id) = server_id {
+            let _ = notifier.conn.call_method(Some(NAME), PATH, Some(NAME), "CloseNotification", &(server_id,));
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod backend {
+    use std::path::Path;
+
+    use super::{NotificationOptions, NotificationSource};
+
+    pub fn show(_notification_id: &str, _source: &NotificationSource, _options: &NotificationOptions, _root: Option<&Path>) -> Result<(), String> {
+        Err("Notifications are not supported on this platform yet".to_string())
+    }
+
+    pub fn close(_notification_id: &str) {}
+}
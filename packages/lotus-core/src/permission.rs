@@ -0,0 +1,108 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use servo::{PermissionFeature, PermissionRequest};
+
+/// Name a permission goes by in events and permissions.json, e.g.
+/// "notifications", "camera", "device-info".
+pub fn name(feature: &PermissionFeature) -> &'static str {
+    match feature {
+        PermissionFeature::Geolocation => "geolocation",
+        PermissionFeature::Notifications => "notifications",
+        PermissionFeature::Push => "push",
+        PermissionFeature::Midi => "midi",
+        PermissionFeature::Camera => "camera",
+        PermissionFeature::Microphone => "microphone",
+        PermissionFeature::Speaker => "speaker",
+        PermissionFeature::DeviceInfo => "device-info",
+        PermissionFeature::BackgroundSync => "background-sync",
+        PermissionFeature::Bluetooth => "bluetooth",
+        PermissionFeature::PersistentStorage => "persistent-storage",
+    }
+}
+
+/// Run in the pane's top-level document when a request arrives. Servo doesn't
+/// say which frame asked, so the request is only known to come from the
+/// top-level origin when the document has no frames at all.
+pub const FRAME_COUNT_SCRIPT: &str = "window.frames.length";
+
+/// A request waiting on Node, with what's needed to remember the answer.
+pub struct PendingPermission {
+    pub window_id: String,
+    pub origin: String,
+    pub permission: String,
+    // Whether the answer may be stored for `origin`: false while the
+    // requesting frame is unknown.
+    pub can_remember: bool,
+    request: Option<PermissionRequest>,
+}
+
+impl PendingPermission {
+    pub fn answer(mut self, allow: bool) {
+        if let Some(request) = self.request.take() {
+            if allow { request.allow() } else { request.deny() }
+        }
+    }
+}
+
+impl Drop for PendingPermission {
+    fn drop(&mut self) {
+        if let Some(request) = self.request.take() {
+            request.deny();
+        }
+    }
+}
+
+// Servo's requests aren't Send; they wait here, on the event loop thread,
+// until Node answers through an engine command.
+thread_local! {
+    static PENDING: RefCell<HashMap<u32, PendingPermission>> = RefCell::new(HashMap::new());
+}
+
+static NEXT_SEQ: AtomicU32 = AtomicU32::new(1);
+
+pub fn hold(window_id: &str, origin: &str, permission: &str, request: PermissionRequest) -> u32 {
+    let seq = NEXT_SEQ.fetch_add(1, Ordering::Relaxed);
+    PENDING.with(|p| p.borrow_mut().insert(seq, PendingPermission {
+        window_id: window_id.to_string(),
+        origin: origin.to_string(),
+        permission: permission.to_string(),
+        can_remember: false,
+        request: Some(request),
+    }));
+    seq
+}
+
+pub fn take(window_id: &str, seq: u32) -> Option<PendingPermission> {
+    PENDING.with(|p| {
+        let mut pending = p.borrow_mut();
+        if pending.get(&seq).is_some_and(|r| r.window_id == window_id) {
+            pending.remove(&seq)
+        } else {
+            None
+        }
+    })
+}
+
+/// Mark a waiting request as coming from its pane's top-level document.
+pub fn set_rememberable(seq: u32) {
+    PENDING.with(|p| {
+        if let Some(pending) = p.borrow_mut().get_mut(&seq) {
+            pending.can_remember = true;
+        }
+    });
+}
+
+/// Deny everything still waiting in a window (or everywhere, with None).
+pub fn discard(window_id: Option<&str>) {
+    let dropped: Vec<PendingPermission> = PENDING.with(|p| {
+        let mut pending = p.borrow_mut();
+        let seqs: Vec<u32> = pending.iter()
+            .filter(|(_, r)| window_id.map_or(true, |id| r.window_id == id))
+            .map(|(seq, _)| *seq)
+            .collect();
+        seqs.into_iter().filter_map(|seq| pending.remove(&seq)).collect()
+    });
+    drop(dropped);
+}
@@ -1,5 +1,7 @@
 use winit::window::Window;
 
+use crate::display::DisplayBounds;
+
 #[cfg(target_os = "linux")]
 pub fn set_always_on_top(window: &Window, always_on_top: bool) {
     use winit::window::WindowLevel;
@@ -44,3 +46,383 @@ pub fn request_attention(window: &Window) {
     use winit::window::UserAttentionType;
     window.request_user_attention(Some(UserAttentionType::Critical));
 }
+
+/// Per-monitor work area (monitor rect minus panels/taskbars), in the same order
+/// as `monitors`. `None` means the platform couldn't tell and the caller should
+/// fall back to the full monitor bounds.
+#[cfg(target_os = "linux")]
+pub fn work_areas(monitors: &[winit::monitor::MonitorHandle]) -> Vec<Option<DisplayBounds>> {
+    use x11rb::connection::Connection;
+    use x11rb::protocol::xproto::{AtomEnum, ConnectionExt as _};
+
+    // EWMH publishes one work area for the whole virtual desktop. Read desktop 0's
+    // rect once and intersect it with each monitor. Wayland sessions without
+    // XWayland have no X connection and fall through to None.
+    let desktop = x11_connection().and_then(|(conn, screen)| {
+        let root = conn.setup().roots.get(*screen)?.root;
+        let atom = conn.intern_atom(false, b"_NET_WORKAREA").ok()?.reply().ok()?.atom;
+        let reply = conn.get_property(false, root, atom, AtomEnum::CARDINAL, 0, 4).ok()?.reply().ok()?;
+        let values: Vec<i64> = reply.value32()?.map(i64::from).collect();
+        if values.len() < 4 {
+            return None;
+        }
+        Some((values[0], values[1], values[2], values[3]))
+    });
+
+    monitors.iter().map(|monitor| {
+        let (dx, dy, dw, dh) = desktop?;
+        let pos = monitor.position();
+        let size = monitor.size();
+        let left = (pos.x as i64).max(dx);
+        let top = (pos.y as i64).max(dy);
+        let right = (pos.x as i64 + size.width as i64).min(dx + dw);
+        let bottom = (pos.y as i64 + size.height as i64).min(dy + dh);
+        if right <= left || bottom <= top {
+            return None;
+        }
+        Some(DisplayBounds {
+            x: left as i32,
+            y: top as i32,
+            width: (right - left) as u32,
+            height: (bottom - top) as u32,
+        })
+    }).collect()
+}
+
+#[cfg(target_os = "windows")]
+pub fn work_areas(monitors: &[winit::monitor::MonitorHandle]) -> Vec<Option<DisplayBounds>> {
+    use winit::platform::windows::MonitorHandleExtWindows;
+    use windows_sys::Win32::Graphics::Gdi::{GetMonitorInfoW, MONITORINFO};
+
+    monitors.iter().map(|monitor| {
+        let mut info: MONITORINFO = unsafe { std::mem::zeroed() };
+        info.cbSize = std::mem::size_of::<MONITORINFO>() as u32;
+        if unsafe { GetMonitorInfoW(monitor.hmonitor(), &mut info) } == 0 {
+            return None;
+        }
+        let rc = info.rcWork;
+        Some(DisplayBounds {
+            x: rc.left,
+            y: rc.top,
+            width: (rc.right - rc.left).max(0) as u32,
+            height: (rc.bottom - rc.top).max(0) as u32,
+        })
+    }).collect()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+pub fn work_areas(monitors: &[winit::monitor::MonitorHandle]) -> Vec<Option<DisplayBounds>> {
+    monitors.iter().map(|_| None).collect()
+}
+
+/// Lazily opened X11 connection shared by the helpers below. `None` under Wayland
+/// or when no X server is reachable.
+#[cfg(target_os = "linux")]
+fn x11_connection() -> Option<&'static (x11rb::rust_connection::RustConnection, usize)> {
+    static CONN: once_cell::sync::OnceCell<Option<(x11rb::rust_connection::RustConnection, usize)>> = once_cell::sync::OnceCell::new();
+    CONN.get_or_init(|| x11rb::connect(None).ok()).as_ref()
+}
+
+/// Call `on_change` whenever X11 reports a monitor change (RandR) or a new
+/// work area (_NET_WORKAREA on the root window), until it returns false.
+/// Listens on its own connection and thread. Returns false without an X
+/// server, so the caller can poll instead.
+#[cfg(target_os = "linux")]
+pub fn watch_displays(on_change: impl Fn() -> bool + Send + 'static) -> bool {
+    use x11rb::connection::Connection;
+    use x11rb::protocol::randr::{ConnectionExt as _, NotifyMask};
+    use x11rb::protocol::xproto::{ChangeWindowAttributesAux, ConnectionExt as _, EventMask};
+    use x11rb::protocol::Event;
+
+    let Ok((conn, screen)) = x11rb::connect(None) else {
+        return false;
+    };
+    let Some(root) = conn.setup().roots.get(screen).map(|s| s.root) else {
+        return false;
+    };
+    let Some(workarea) = conn.intern_atom(false, b"_NET_WORKAREA").ok().and_then(|c| c.reply().ok()).map(|r| r.atom) else {
+        return false;
+    };
+    let attrs = ChangeWindowAttributesAux::new().event_mask(EventMask::PROPERTY_CHANGE);
+    if conn.change_window_attributes(root, &attrs).is_err() {
+        return false;
+    }
+    // Without RandR, work area changes are still reported.
+    if conn.randr_query_version(1, 2).ok().and_then(|c| c.reply().ok()).is_some() {
+        let _ = conn.randr_select_input(root, NotifyMask::SCREEN_CHANGE | NotifyMask::CRTC_CHANGE | NotifyMask::OUTPUT_CHANGE);
+    }
+    if conn.flush().is_err() {
+        return false;
+    }
+
+    std::thread::spawn(move || {
+        while let Ok(event) = conn.wait_for_event() {
+            let changed = match event {
+                Event::PropertyNotify(e) => e.atom == workarea,
+                Event::RandrScreenChangeNotify(_) | Event::RandrNotify(_) => true,
+                _ => false,
+            };
+            if changed && !on_change() {
+                break;
+            }
+        }
+    });
+    true
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn watch_displays(_on_change: impl Fn() -> bool + Send + 'static) -> bool {
+    false
+}
+
+/// X11 window id for a winit window, or `None` under Wayland.
+#[cfg(target_os = "linux")]
+pub fn x11_window_id(window: &Window) -> Option<u32> {
+    use winit::raw_window_handle::{HasWindowHandle, RawWindowHandle};
+    match window.window_handle().ok()?.as_raw() {
+        RawWindowHandle::Xlib(h) => Some(h.window as u32),
+        RawWindowHandle::Xcb(h) => Some(h.window.get()),
+        _ => None,
+    }
+}
+
+#[cfg(target_os = "linux")]
+static X11_EVENT_LOOP: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Record whether winit picked X11 (not Wayland), for checks made off the event loop.
+#[cfg(target_os = "linux")]
+pub fn set_x11_event_loop(x11: bool) {
+    X11_EVENT_LOOP.store(x11, std::sync::atomic::Ordering::Relaxed);
+}
+
+#[cfg(target_os = "linux")]
+pub fn x11_event_loop() -> bool {
+    X11_EVENT_LOOP.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// Make `owner` the owner of the window about to be created. On Windows this is a
+/// real owned window (stays above the owner, hides and minimizes with it); other
+/// platforms set this up after creation in `set_transient_for`.
+#[cfg(target_os = "windows")]
+pub fn with_owner(attrs: winit::window::WindowAttributes, owner: &Window) -> winit::window::WindowAttributes {
+    use winit::platform::windows::WindowAttributesExtWindows;
+    use winit::raw_window_handle::{HasWindowHandle, RawWindowHandle};
+    match owner.window_handle().map(|h| h.as_raw()) {
+        Ok(RawWindowHandle::Win32(h)) => attrs.with_owner_window(h.hwnd.get()),
+        _ => attrs,
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn with_owner(attrs: winit::window::WindowAttributes, _owner: &Window) -> winit::window::WindowAttributes {
+    attrs
+}
+
+/// Set WM_TRANSIENT_FOR (and _NET_WM_STATE_MODAL for modal children) so the window
+/// manager stacks, minimizes and focuses the child together with its owner. Must be
+/// called before the child is first mapped. No-op on Wayland.
+#[cfg(target_os = "linux")]
+pub fn set_transient_for(child: &Window, owner: &Window, modal: bool) {
+    use x11rb::connection::Connection;
+    use x11rb::protocol::xproto::{AtomEnum, ConnectionExt as _, PropMode};
+    use x11rb::wrapper::ConnectionExt as _;
+
+    let (Some(child_id), Some(owner_id)) = (x11_window_id(child), x11_window_id(owner)) else {
+        return;
+    };
+    let Some((conn, _)) = x11_connection() else {
+        return;
+    };
+
+    let _ = conn.change_property32(PropMode::REPLACE, child_id, AtomEnum::WM_TRANSIENT_FOR, AtomEnum::WINDOW, &[owner_id]);
+    if modal {
+        let state = conn.intern_atom(false, b"_NET_WM_STATE").ok().and_then(|c| c.reply().ok());
+        let modal_atom = conn.intern_atom(false, b"_NET_WM_STATE_MODAL").ok().and_then(|c| c.reply().ok());
+        if let (Some(state), Some(modal_atom)) = (state, modal_atom) {
+            let _ = conn.change_property32(PropMode::APPEND, child_id, state.atom, AtomEnum::ATOM, &[modal_atom.atom]);
+        }
+    }
+    let _ = conn.flush();
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_transient_for(_child: &Window, _owner: &Window, _modal: bool) {
+    // Windows handles ownership at creation (see `with_owner`).
+}
+
+/// A toplevel surface exported through xdg-foreign. The handle stays valid for
+/// other clients (portals, the GTK thread) until this is dropped.
+#[cfg(target_os = "linux")]
+pub struct WaylandExport {
+    pub handle: String,
+    exported: wayland_protocols::xdg::foreign::zv2::client::zxdg_exported_v2::ZxdgExportedV2,
+    conn: wayland_client::Connection,
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for WaylandExport {
+    fn drop(&mut self) {
+        self.exported.destroy();
+        let _ = self.conn.flush();
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod wayland_export {
+    use wayland_client::globals::GlobalListContents;
+    use wayland_client::protocol::wl_registry::{self, WlRegistry};
+    use wayland_client::{Connection, Dispatch, QueueHandle};
+    use wayland_protocols::xdg::foreign::zv2::client::zxdg_exported_v2::{self, ZxdgExportedV2};
+    use wayland_protocols::xdg::foreign::zv2::client::zxdg_exporter_v2::{self, ZxdgExporterV2};
+
+    #[derive(Default)]
+    pub struct State {
+        pub handle: Option<String>,
+    }
+
+    impl Dispatch<WlRegistry, GlobalListContents> for State {
+        fn event(_: &mut Self, _: &WlRegistry, _: wl_registry::Event, _: &GlobalListContents, _: &Connection, _: &QueueHandle<Self>) {}
+    }
+
+    impl Dispatch<ZxdgExporterV2, ()> for State {
+        fn event(_: &mut Self, _: &ZxdgExporterV2, _: zxdg_exporter_v2::Event, _: &(), _: &Connection, _: &QueueHandle<Self>) {}
+    }
+
+    impl Dispatch<ZxdgExportedV2, ()> for State {
+        fn event(state: &mut Self, _: &ZxdgExportedV2, event: zxdg_exported_v2::Event, _: &(), _: &Connection, _: &QueueHandle<Self>) {
+            if let zxdg_exported_v2::Event::Handle { handle } = event {
+                state.handle = Some(handle);
+            }
+        }
+    }
+}
+
+/// Export a Wayland toplevel (winit's display and surface pointers) so another
+/// client can parent a dialog to it. Runs on its own event queue, so any thread
+/// will do. `None` if the compositor lacks xdg-foreign.
+#[cfg(target_os = "linux")]
+pub fn export_wayland_surface(display: *mut std::ffi::c_void, surface: *mut std::ffi::c_void) -> Option<WaylandExport> {
+    use wayland_backend::client::{Backend, ObjectId};
+    use wayland_client::globals::registry_queue_init;
+    use wayland_client::protocol::wl_surface::WlSurface;
+    use wayland_client::{Connection, Proxy};
+    use wayland_protocols::xdg::foreign::zv2::client::zxdg_exporter_v2::ZxdgExporterV2;
+
+    let conn = Connection::from_backend(unsafe { Backend::from_foreign_display(display.cast()) });
+    let (globals, mut queue) = registry_queue_init::<wayland_export::State>(&conn).ok()?;
+    let qh = queue.handle();
+    let exporter: ZxdgExporterV2 = globals.bind(&qh, 1..=1, ()).ok()?;
+    let surface_id = unsafe { ObjectId::from_ptr(WlSurface::interface(), surface.cast()) }.ok()?;
+    let surface = WlSurface::from_id(&conn, surface_id).ok()?;
+    let exported = exporter.export_toplevel(&surface, &qh, ());
+    exporter.destroy();
+
+    let mut state = wayland_export::State::default();
+    queue.roundtrip(&mut state).ok()?;
+    let Some(handle) = state.handle else {
+        exported.destroy();
+        let _ = conn.flush();
+        return None;
+    };
+    Some(WaylandExport { handle, exported, conn })
+}
+
+/// Run `f` on the GTK thread, starting GTK the first time. winit windows have no
+/// toolkit, so context menus and dialog fallbacks live on their own GTK main loop.
+/// Returns false if GTK couldn't be initialized.
+#[cfg(target_os = "linux")]
+pub fn run_on_gtk_thread(f: impl FnOnce() + Send + 'static) -> bool {
+    static READY: once_cell::sync::OnceCell<bool> = once_cell::sync::OnceCell::new();
+    let ready = *READY.get_or_init(|| {
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let ok = gtk::init().is_ok();
+            let _ = tx.send(ok);
+            if ok {
+                gtk::main();
+            }
+        });
+        rx.recv().unwrap_or(false)
+    });
+    if ready {
+        gtk::glib::MainContext::default().invoke(f);
+    }
+    ready
+}
+
+/// Set a UTF-8 string property on an X11 window. No-op on Wayland.
+#[cfg(target_os = "linux")]
+pub fn set_x11_string_property(window: &Window, name: &str, value: &str) {
+    use x11rb::connection::Connection;
+    use x11rb::protocol::xproto::{ConnectionExt as _, PropMode};
+    use x11rb::wrapper::ConnectionExt as _;
+
+    let (Some(id), Some((conn, _))) = (x11_window_id(window), x11_connection()) else {
+        return;
+    };
+    let property = conn.intern_atom(false, name.as_bytes()).ok().and_then(|c| c.reply().ok());
+    let utf8 = conn.intern_atom(false, b"UTF8_STRING").ok().and_then(|c| c.reply().ok());
+    if let (Some(property), Some(utf8)) = (property, utf8) {
+        let _ = conn.change_property8(PropMode::REPLACE, id, property.atom, utf8.atom, value.as_bytes());
+        let _ = conn.flush();
+    }
+}
+
+/// Apply whole-window opacity through the window manager. Returns false when the
+/// platform can't, so the caller fades the window in the compositor instead.
+#[cfg(target_os = "linux")]
+pub fn set_window_opacity(window: &Window, opacity: f32) -> bool {
+    use x11rb::connection::Connection;
+    use x11rb::protocol::xproto::{AtomEnum, ConnectionExt as _, PropMode};
+    use x11rb::wrapper::ConnectionExt as _;
+
+    let (Some(id), Some((conn, _))) = (x11_window_id(window), x11_connection()) else {
+        return false;
+    };
+    let Some(atom) = conn.intern_atom(false, b"_NET_WM_WINDOW_OPACITY").ok().and_then(|c| c.reply().ok()) else {
+        return false;
+    };
+    let ok = if opacity >= 1.0 {
+        conn.delete_property(id, atom.atom).is_ok()
+    } else {
+        let value = (opacity as f64 * u32::MAX as f64) as u32;
+        conn.change_property32(PropMode::REPLACE, id, atom.atom, AtomEnum::CARDINAL, &[value]).is_ok()
+    };
+    ok && conn.flush().is_ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_window_opacity(_window: &Window, _opacity: f32) -> bool {
+    false
+}
+
+/// Global cursor position in physical desktop pixels, for polling over `window`.
+/// `None` for Wayland windows: XWayland's pointer isn't in their coordinates.
+#[cfg(target_os = "linux")]
+pub fn cursor_position(window: &Window) -> Option<winit::dpi::PhysicalPosition<i32>> {
+    use x11rb::connection::Connection;
+    use x11rb::protocol::xproto::ConnectionExt as _;
+
+    x11_window_id(window)?;
+    let (conn, screen) = x11_connection()?;
+    let root = conn.setup().roots.get(*screen)?.root;
+    let reply = conn.query_pointer(root).ok()?.reply().ok()?;
+    Some(winit::dpi::PhysicalPosition::new(reply.root_x as i32, reply.root_y as i32))
+}
+
+#[cfg(target_os = "windows")]
+pub fn cursor_position(_window: &Window) -> Option<winit::dpi::PhysicalPosition<i32>> {
+    use windows_sys::Win32::Foundation::POINT;
+    use windows_sys::Win32::UI::WindowsAndMessaging::GetCursorPos;
+
+    let mut point = POINT { x: 0, y: 0 };
+    if unsafe { GetCursorPos(&mut point) } == 0 {
+        return None;
+    }
+    Some(winit::dpi::PhysicalPosition::new(point.x, point.y))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+pub fn cursor_position(_window: &Window) -> Option<winit::dpi::PhysicalPosition<i32>> {
+    None
+}
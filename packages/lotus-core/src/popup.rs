@@ -0,0 +1,142 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
+
+use servo::{NavigationRequest, OffscreenRenderingContext, WebView, WindowRenderingContext};
+
+use crate::navigation::PendingNavigation;
+
+/// What a pane delegate needs to create `window.open` popups: Servo wants
+/// the new webview back synchronously, and it can only be composited in
+/// the opener's window (each window has its own GL context).
+#[derive(Clone)]
+pub struct PopupHost {
+    pub servo: servo::Servo,
+    pub rendering_context: Rc<WindowRenderingContext>,
+}
+
+/// A popup created hidden in its opener's window, parked until Node decides
+/// whether it becomes a pane.
+pub struct Popup {
+    pub window_id: String,
+    pub opener_pane: String,
+    pub webview: WebView,
+    pub offscreen_ctx: Rc<OffscreenRenderingContext>,
+    pub ready_to_repaint: Arc<AtomicBool>,
+    // The popup's first navigation, held until Node adopts it.
+    pub navigation: Option<PendingNavigation>,
+    // What the opener's page recorded about the window.open call, once read.
+    pub hints: Option<OpenHints>,
+    pub announced: bool,
+}
+
+/// window.open's target and features as the opener's page recorded them.
+/// Servo doesn't pass them on, so the page could have written anything here.
+#[derive(Debug)]
+pub struct OpenHints {
+    pub target: String,
+    pub features: String,
+    // Whether a URL was given; a bare window.open() never navigates.
+    pub navigates: bool,
+}
+
+/// What Node is told about a popup: its URL comes from Servo's navigation
+/// request, never from the page.
+pub struct Announcement {
+    pub opener_pane: String,
+    pub url: String,
+    pub hints: OpenHints,
+}
+
+impl Popup {
+    /// Announce once the hints are in and, unless it's a bare window.open(),
+    /// the first navigation has been held.
+    fn announcement(&mut self) -> Option<Announcement> {
+        if self.announced {
+            return None;
+        }
+        let url = match &self.navigation {
+            Some(navigation) => navigation.url(),
+            None if self.hints.as_ref().is_some_and(|h| !h.navigates) => String::new(),
+            None => return None,
+        };
+        let hints = self.hints.take()?;
+        self.announced = true;
+        Some(Announcement { opener_pane: self.opener_pane.clone(), url, hints })
+    }
+}
+
+// WebViews aren't Send, so popups live on the event loop thread, where both
+// Servo's delegate calls and engine commands run.
+thread_local! {
+    static POPUPS: RefCell<HashMap<u32, Popup>> = RefCell::new(HashMap::new());
+}
+
+static NEXT_POPUP: AtomicU32 = AtomicU32::new(1);
+
+pub fn next_seq() -> u32 {
+    NEXT_POPUP.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Pane id the popup's delegate reports events under, and keeps if adopted.
+pub fn pane_id(seq: u32) -> String {
+    format!("popup-{}", seq)
+}
+
+pub fn park(seq: u32, popup: Popup) {
+    POPUPS.with(|p| p.borrow_mut().insert(seq, popup));
+}
+
+/// Hold the first navigation of a parked popup, or hand the request back if
+/// `pane_id` isn't a popup waiting on Node. Returns the popup's announcement
+/// if this was what it waited for.
+pub fn hold_navigation(window_id: &str, pane_id: &str, request: NavigationRequest) -> Result<Option<Announcement>, NavigationRequest> {
+    let Some(seq) = pane_id.strip_prefix("popup-").and_then(|s| s.parse::<u32>().ok()) else {
+        return Err(request);
+    };
+    POPUPS.with(|p| match p.borrow_mut().get_mut(&seq) {
+        Some(popup) if popup.window_id == window_id && popup.navigation.is_none() => {
+            popup.navigation = Some(PendingNavigation::new(request));
+            Ok(popup.announcement())
+        }
+        _ => Err(request),
+    })
+}
+
+/// Record the opener's hints for a popup. Returns its announcement if the
+/// popup is ready (a bare window.open(), or the navigation came first).
+pub fn set_hints(seq: u32, hints: OpenHints) -> Option<Announcement> {
+    POPUPS.with(|p| {
+        let mut popups = p.borrow_mut();
+        let popup = popups.get_mut(&seq)?;
+        popup.hints = Some(hints);
+        popup.announcement()
+    })
+}
+
+pub fn take(window_id: &str, seq: u32) -> Option<Popup> {
+    POPUPS.with(|p| {
+        let mut popups = p.borrow_mut();
+        if popups.get(&seq).is_some_and(|popup| popup.window_id == window_id) {
+            popups.remove(&seq)
+        } else {
+            None
+        }
+    })
+}
+
+/// Close every popup still parked in a window (or all, with None).
+pub fn discard(window_id: Option<&str>) {
+    // Dropped outside the borrow: closing a webview can call back into delegates.
+    let dropped: Vec<Popup> = POPUPS.with(|p| {
+        let mut popups = p.borrow_mut();
+        let seqs: Vec<u32> = popups.iter()
+            .filter(|(_, popup)| window_id.map_or(true, |id| popup.window_id == id))
+            .map(|(seq, _)| *seq)
+            .collect();
+        seqs.into_iter().filter_map(|seq| popups.remove(&seq)).collect()
+    });
+    drop(dropped);
+}
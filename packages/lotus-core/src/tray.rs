@@ -0,0 +1,363 @@
+use napi_derive::napi;
+
+use crate::menu::MenuItemOptions;
+use crate::{icon, EngineCommand, EVENT_LOOP_PROXY};
+
+/// Tray icon pixels as ARGB32 in network byte order, which is what the
+/// StatusNotifierItem spec uses and is easy to convert from on other platforms.
+#[derive(Debug, Clone)]
+pub struct TrayImage {
+    pub width: i32,
+    pub height: i32,
+    pub argb: Vec<u8>,
+}
+
+impl TrayImage {
+    pub fn from_png(bytes: &[u8]) -> Result<Self, String> {
+        let (rgba, width, height) = icon::decode_png_rgba(bytes)?;
+        Ok(TrayImage {
+            width: width as i32,
+            height: height as i32,
+            argb: rgba.chunks_exact(4).flat_map(|p| [p[3], p[0], p[1], p[2]]).collect(),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum TrayCommand {
+    Create(Option<TrayImage>, Option<String>), // icon, tooltip
+    SetIcon(Option<TrayImage>),
+    SetTooltip(String),
+    SetMenu(Vec<MenuItemOptions>),
+    Destroy,
+}
+
+#[derive(Debug)]
+pub enum TrayEvent {
+    Click(&'static str, i32, i32), // button, x, y
+    MenuActivate(String, Option<bool>), // item_id, checked
+    Error(String), // the tray couldn't be created; it's gone
+}
+
+impl TrayEvent {
+    pub fn to_json(&self, tray_id: &str) -> serde_json::Value {
+        match self {
+            TrayEvent::Click(button, x, y) => serde_json::json!({
+                "event": "tray-click",
+                "tray_id": tray_id,
+                "button": button,
+                "x": x,
+                "y": y
+            }),
+            TrayEvent::MenuActivate(item_id, checked) => serde_json::json!({
+                "event": "tray-menu-activate",
+                "tray_id": tray_id,
+                "item_id": item_id,
+                "checked": checked
+            }),
+            TrayEvent::Error(message) => serde_json::json!({
+                "event": "tray-error",
+                "tray_id": tray_id,
+                "message": message
+            }),
+        }
+    }
+}
+
+fn send_event(tray_id: &str, event: TrayEvent) {
+    if let Some(proxy) = EVENT_LOOP_PROXY.get() {
+        let _ = proxy.send_event(EngineCommand::TrayEvent(tray_id.to_string(), event));
+    }
+}
+
+fn send_command(tray_id: &str, command: TrayCommand) {
+    if let Some(proxy) = EVENT_LOOP_PROXY.get() {
+        let _ = proxy.send_event(EngineCommand::Tray(tray_id.to_string(), command));
+    }
+}
+
+fn image_arg(icon: napi::Either<napi::bindgen_prelude::Buffer, String>) -> napi::Result<TrayImage> {
+    let bytes = match icon {
+        napi::Either::A(buffer) => buffer.to_vec(),
+        napi::Either::B(path) => icon::read_image(&path, None).map_err(napi::Error::from_reason)?,
+    };
+    TrayImage::from_png(&bytes).map_err(napi::Error::from_reason)
+}
+
+/// A system tray icon. Lives on the event loop thread; this handle only sends commands.
+#[napi]
+pub struct Tray {
+    id: String,
+}
+
+#[napi]
+impl Tray {
+    #[napi(constructor)]
+    pub fn new(icon: Option<napi::Either<napi::bindgen_prelude::Buffer, String>>, tooltip: Option<String>) -> napi::Result<Self> {
+        let image = icon.map(image_arg).transpose()?;
+        let id = uuid::Uuid::new_v4().to_string();
+        send_command(&id, TrayCommand::Create(image, tooltip));
+        Ok(Tray { id })
+    }
+
+    #[napi]
+    pub fn get_id(&self) -> String {
+        self.id.clone()
+    }
+
+    #[napi]
+    pub fn set_icon(&self, icon: Option<napi::Either<napi::bindgen_prelude::Buffer, String>>) -> napi::Result<()> {
+        let image = icon.map(image_arg).transpose()?;
+        send_command(&self.id, TrayCommand::SetIcon(image));
+        Ok(())
+    }
+
+    #[napi]
+    pub fn set_tooltip(&self, tooltip: String) {
+        send_command(&self.id, TrayCommand::SetTooltip(tooltip));
+    }
+
+    #[napi]
+    pub fn set_menu(&self, items: Vec<MenuItemOptions>) {
+        send_command(&self.id, TrayCommand::SetMenu(items));
+    }
+
+    #[napi]
+    pub fn destroy(&self) {
+        send_command(&self.id, TrayCommand::Destroy);
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub use linux::TrayHandle;
+
+#[cfg(not(target_os = "linux"))]
+pub struct TrayHandle;
+
+#[cfg(not(target_os = "linux"))]
+impl TrayHandle {
+    pub fn new(_tray_id: &str, _icon: Option<TrayImage>, _tooltip: Option<String>) -> Result<Self, String> {
+        Err("System tray is not supported on this platform yet".to_string())
+    }
+    pub fn set_icon(&self, _icon: Option<TrayImage>) {}
+    pub fn set_tooltip(&self, _tooltip: String) {}
+    pub fn set_menu(&self, _items: Vec<MenuItemOptions>) {}
+}
+
+/// StatusNotifierItem on the session bus, with its menu exported through `dbusmenu`.
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::{mpsc, Arc, Mutex};
+
+    use zbus::object_server::SignalContext;
+    use zbus::zvariant::ObjectPath;
+
+    use super::{send_event, TrayEvent, TrayImage};
+    use crate::dbusmenu::{self, DbusMenu, MenuModel};
+    use crate::menu::MenuItemOptions;
+
+    const ITEM_PATH: &str = "/StatusNotifierItem";
+    const MENU_PATH: &str = "/MenuBar";
+
+    static NEXT_ITEM: AtomicU32 = AtomicU32::new(1);
+
+    struct TrayState {
+        tray_id: String,
+        icon: Option<TrayImage>,
+        tooltip: String,
+    }
+
+    struct StatusNotifierItem {
+        state: Arc<Mutex<TrayState>>,
+    }
+
+    #[zbus::interface(name = "org.kde.StatusNotifierItem")]
+    impl StatusNotifierItem {
+        fn activate(&self, x: i32, y: i32) {
+            let tray_id = self.state.lock().unwrap().tray_id.clone();
+            send_event(&tray_id, TrayEvent::Click("left", x, y));
+        }
+
+        fn secondary_activate(&self, x: i32, y: i32) {
+            let tray_id = self.state.lock().unwrap().tray_id.clone();
+            send_event(&tray_id, TrayEvent::Click("middle", x, y));
+        }
+
+        fn context_menu(&self, x: i32, y: i32) {
+            let tray_id = self.state.lock().unwrap().tray_id.clone();
+            send_event(&tray_id, TrayEvent::Click("right", x, y));
+        }
+
+        fn scroll(&self, _delta: i32, _orientation: &str) {}
+
+        #[zbus(property)]
+        fn category(&self) -> &str {
+            "ApplicationStatus"
+        }
+
+        #[zbus(property)]
+        fn id(&self) -> String {
+            self.state.lock().unwrap().tray_id.clone()
+        }
+
+        #[zbus(property)]
+        fn title(&self) -> String {
+            self.state.lock().unwrap().tooltip.clone()
+        }
+
+        #[zbus(property)]
+        fn status(&self) -> &str {
+            "Active"
+        }
+
+        #[zbus(property)]
+        fn icon_name(&self) -> &str {
+            ""
+        }
+
+        #[zbus(property)]
+        fn icon_pixmap(&self) -> Vec<(i32, i32, Vec<u8>)> {
+            let state = self.state.lock().unwrap();
+            state.icon.iter().map(|i| (i.width, i.height, i.argb.clone())).collect()
+        }
+
+        #[zbus(property)]
+        fn tool_tip(&self) -> (String, Vec<(i32, i32, Vec<u8>)>, String, String) {
+            let state = self.state.lock().unwrap();
+            (String::new(), Vec::new(), state.tooltip.clone(), String::new())
+        }
+
+        #[zbus(property)]
+        fn item_is_menu(&self) -> bool {
+            false
+        }
+
+        #[zbus(property)]
+        fn menu(&self) -> ObjectPath<'static> {
+            ObjectPath::from_static_str_unchecked(MENU_PATH)
+        }
+
+        #[zbus(signal)]
+        async fn new_icon(ctxt: &SignalContext<'_>) -> zbus::Result<()>;
+
+        #[zbus(signal)]
+        async fn new_tool_tip(ctxt: &SignalContext<'_>) -> zbus::Result<()>;
+
+        #[zbus(signal)]
+        async fn new_title(ctxt: &SignalContext<'_>) -> zbus::Result<()>;
+    }
+
+    enum Update {
+        Icon(Option<TrayImage>),
+        Tooltip(String),
+        Menu(Vec<MenuItemOptions>),
+    }
+
+    /// The item is exported and updated on its own thread, so session bus
+    /// round trips never stall the event loop. Dropping the handle ends the
+    /// thread, which closes the connection and takes the item off the bus.
+    pub struct TrayHandle {
+        updates: mpsc::Sender<Update>,
+    }
+
+    impl TrayHandle {
+        pub fn new(tray_id: &str, icon: Option<TrayImage>, tooltip: Option<String>) -> Result<Self, String> {
+            let (updates, rx) = mpsc::channel();
+            let tray_id = tray_id.to_string();
+            std::thread::Builder::new()
+                .name(format!("tray-{}", tray_id))
+                .spawn(move || {
+                    let state = Arc::new(Mutex::new(TrayState {
+                        tray_id: tray_id.clone(),
+                        icon,
+                        tooltip: tooltip.unwrap_or_default(),
+                    }));
+                    match Item::export(&tray_id, state) {
+                        Ok(item) => {
+                            for update in rx {
+                                item.apply(update);
+                            }
+                        }
+                        Err(e) => send_event(&tray_id, TrayEvent::Error(e)),
+                    }
+                })
+                .map_err(|e| format!("Failed to start tray thread: {}", e))?;
+            Ok(TrayHandle { updates })
+        }
+
+        pub fn set_icon(&self, icon: Option<TrayImage>) {
+            let _ = self.updates.send(Update::Icon(icon));
+        }
+
+        pub fn set_tooltip(&self, tooltip: String) {
+            let _ = self.updates.send(Update::Tooltip(tooltip));
+        }
+
+        pub fn set_menu(&self, items: Vec<MenuItemOptions>) {
+            let _ = self.updates.send(Update::Menu(items));
+        }
+    }
+
+    struct Item {
+        conn: zbus::blocking::Connection,
+        state: Arc<Mutex<TrayState>>,
+        menu: Arc<Mutex<MenuModel>>,
+    }
+
+    impl Item {
+        fn export(tray_id: &str, state: Arc<Mutex<TrayState>>) -> Result<Self, String> {
+            let menu = Arc::new(Mutex::new(MenuModel::new()));
+            let menu_tray_id = tray_id.to_string();
+            let on_activate: dbusmenu::ActivateFn = Arc::new(move |item_id, checked| {
+                send_event(&menu_tray_id, TrayEvent::MenuActivate(item_id, checked));
+            });
+
+            let name = format!("org.kde.StatusNotifierItem-{}-{}", std::process::id(), NEXT_ITEM.fetch_add(1, Ordering::Relaxed));
+            let conn = zbus::blocking::connection::Builder::session()
+                .and_then(|b| b.name(name.as_str()))
+                .and_then(|b| b.serve_at(ITEM_PATH, StatusNotifierItem { state: state.clone() }))
+                .and_then(|b| b.serve_at(MENU_PATH, DbusMenu::new(menu.clone(), on_activate)))
+                .and_then(|b| b.build())
+                .map_err(|e| format!("Failed to export tray on the session bus: {}", e))?;
+
+            // No watcher means no tray host (e.g. stock GNOME). Node still gets a
+            // working handle; the icon just isn't shown anywhere.
+            if let Err(e) = conn.call_method(
+                Some("org.kde.StatusNotifierWatcher"),
+                "/StatusNotifierWatcher",
+                Some("org.kde.StatusNotifierWatcher"),
+                "RegisterStatusNotifierItem",
+                &(name.as_str(),),
+            ) {
+                log::warn!("Rust: No StatusNotifierWatcher on the session bus, tray {} won't be shown: {}", tray_id, e);
+            }
+
+            Ok(Item { conn, state, menu })
+        }
+
+        fn interface(&self) -> zbus::Result<zbus::blocking::object_server::InterfaceRef<StatusNotifierItem>> {
+            self.conn.object_server().interface::<_, StatusNotifierItem>(ITEM_PATH)
+        }
+
+        // Hosts re-read properties when they see the matching New* signal.
+        fn apply(&self, update: Update) {
+            match update {
+                Update::Icon(icon) => {
+                    self.state.lock().unwrap().icon = icon;
+                    if let Ok(item) = self.interface() {
+                        let _ = zbus::block_on(StatusNotifierItem::new_icon(item.signal_context()));
+                    }
+                }
+                Update::Tooltip(tooltip) => {
+                    self.state.lock().unwrap().tooltip = tooltip;
+                    if let Ok(item) = self.interface() {
+                        let _ = zbus::block_on(StatusNotifierItem::new_tool_tip(item.signal_context()));
+                        let _ = zbus::block_on(StatusNotifierItem::new_title(item.signal_context()));
+                    }
+                }
+                Update::Menu(items) => dbusmenu::set_items(&self.conn, MENU_PATH, &self.menu, items),
+            }
+        }
+    }
+}
@@ -3,6 +3,8 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
+use crate::display::{self, DisplayInfo};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WindowState {
     pub x: i32,
@@ -11,6 +13,12 @@ pub struct WindowState {
     pub height: u32,
     pub maximized: bool,
     pub fullscreen: bool,
+    // Display the window was on when saved, and that display's scale factor.
+    // Optional so state files written by older versions still load.
+    #[serde(default)]
+    pub display_id: Option<String>,
+    #[serde(default)]
+    pub scale_factor: Option<f64>,
 }
 
 pub struct WindowStateManager {
@@ -43,8 +51,64 @@ impl WindowStateManager {
         self.save_to_disk();
     }
     
-    pub fn get_window_state(&self, key: &str) -> Option<&WindowState> {
-        self.states.get(key)
+    /// Look up the saved state for `key`, adjusted to the current monitor set so the
+    /// window never reopens off-screen. The window goes back to the display it was saved
+    /// on at the same logical size. If that display is gone (or the state predates
+    /// display ids) but the rect still overlaps a display, it is kept in place, nudged
+    /// into that display's work area; only a rect that is entirely off-screen is
+    /// re-centered on the primary display.
+    pub fn get_window_state(&self, key: &str, displays: &[DisplayInfo]) -> Option<WindowState> {
+        let saved = self.states.get(key)?;
+        if displays.is_empty() {
+            return Some(saved.clone());
+        }
+
+        let mut state = saved.clone();
+        let saved_rect = display::DisplayBounds { x: saved.x, y: saved.y, width: saved.width, height: saved.height };
+
+        let original = saved.display_id.as_ref()
+            .and_then(|id| displays.iter().find(|d| &d.id == id));
+        let overlapping = displays.iter()
+            .filter(|d| d.bounds.intersection_area(&saved_rect) > 0)
+            .max_by_key(|d| d.bounds.intersection_area(&saved_rect));
+        let on_screen = original.or(overlapping);
+        let target = on_screen
+            .or_else(|| displays.iter().find(|d| d.is_primary))
+            .unwrap_or(&displays[0]);
+
+        // Keep the logical size stable across scale factor changes (e.g. the display
+        // was switched from 100% to 150% while the app was closed).
+        if let Some(saved_scale) = saved.scale_factor {
+            if saved_scale > 0.0 && (saved_scale - target.scale_factor).abs() > f64::EPSILON {
+                let ratio = target.scale_factor / saved_scale;
+                state.width = (saved.width as f64 * ratio).round() as u32;
+                state.height = (saved.height as f64 * ratio).round() as u32;
+                if original.is_some() {
+                    let dx = (saved.x - target.bounds.x) as f64 * ratio;
+                    let dy = (saved.y - target.bounds.y) as f64 * ratio;
+                    state.x = target.bounds.x + dx.round() as i32;
+                    state.y = target.bounds.y + dy.round() as i32;
+                }
+            }
+        }
+
+        let wa = &target.work_area;
+        state.width = state.width.min(wa.width).max(1);
+        state.height = state.height.min(wa.height).max(1);
+
+        if on_screen.is_some() {
+            // Still on a display: nudge the rect back inside its work area.
+            state.x = state.x.clamp(wa.x, wa.x + (wa.width - state.width) as i32);
+            state.y = state.y.clamp(wa.y, wa.y + (wa.height - state.height) as i32);
+        } else {
+            let pos = display::centered_position(target, state.width, state.height);
+            state.x = pos.x;
+            state.y = pos.y;
+        }
+
+        state.display_id = Some(target.id.clone());
+        state.scale_factor = Some(target.scale_factor);
+        Some(state)
     }
     
     fn load_from_disk(path: &PathBuf) -> HashMap<String, WindowState> {
@@ -64,3 +128,198 @@ impl WindowStateManager {
         }
     }
 }
+
+/// Page zoom per origin, kept in zoom-levels.json next to window-state.json.
+/// Like window state, it's written when a window closes, not on every change.
+pub struct ZoomLevels {
+    levels: HashMap<String, f32>,
+    config_path: PathBuf,
+    dirty: bool,
+}
+
+impl ZoomLevels {
+    pub fn new(app_identifier: &str) -> Self {
+        let config_path = dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(app_identifier)
+            .join("zoom-levels.json");
+        let levels = fs::read_to_string(&config_path).ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        ZoomLevels { levels, config_path, dirty: false }
+    }
+
+    pub fn get(&self, origin: &str) -> Option<f32> {
+        self.levels.get(origin).copied()
+    }
+
+    /// Remember `zoom` for `origin`; the default level is stored as no entry.
+    pub fn set(&mut self, origin: &str, zoom: f32) {
+        let changed = if (zoom - 1.0).abs() < 0.001 {
+            self.levels.remove(origin).is_some()
+        } else {
+            self.levels.insert(origin.to_string(), zoom) != Some(zoom)
+        };
+        self.dirty |= changed;
+    }
+
+    /// Write zoom-levels.json if anything changed since the last write.
+    pub fn flush(&mut self) {
+        if !std::mem::take(&mut self.dirty) {
+            return;
+        }
+        if let Some(parent) = self.config_path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Ok(json) = serde_json::to_string_pretty(&self.levels) {
+            let _ = fs::write(&self.config_path, json);
+        }
+    }
+}
+
+/// Permission answers remembered per origin, in permissions.json next to
+/// window-state.json: origin -> permission name -> granted.
+pub struct PermissionDecisions {
+    decisions: HashMap<String, HashMap<String, bool>>,
+    config_path: PathBuf,
+}
+
+impl PermissionDecisions {
+    pub fn new(app_identifier: &str) -> Self {
+        let config_path = dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(app_identifier)
+            .join("permissions.json");
+        let decisions = fs::read_to_string(&config_path).ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        PermissionDecisions { decisions, config_path }
+    }
+
+    pub fn get(&self, origin: &str, permission: &str) -> Option<bool> {
+        self.decisions.get(origin).and_then(|d| d.get(permission)).copied()
+    }
+
+    pub fn all(&self) -> &HashMap<String, HashMap<String, bool>> {
+        &self.decisions
+    }
+
+    /// Remember a decision; None forgets it so the next request asks again.
+    pub fn set(&mut self, origin: &str, permission: &str, granted: Option<bool>) {
+        let changed = match granted {
+            Some(granted) => self.decisions.entry(origin.to_string()).or_default()
+                .insert(permission.to_string(), granted) != Some(granted),
+            None => {
+                let removed = self.decisions.get_mut(origin).and_then(|d| d.remove(permission)).is_some();
+                if self.decisions.get(origin).is_some_and(|d| d.is_empty()) {
+                    self.decisions.remove(origin);
+                }
+                removed
+            }
+        };
+        if changed {
+            self.save();
+        }
+    }
+
+    /// Forget everything remembered for `origin`, or for every origin.
+    pub fn clear(&mut self, origin: Option<&str>) {
+        let changed = match origin {
+            Some(origin) => self.decisions.remove(origin).is_some(),
+            None => !std::mem::take(&mut self.decisions).is_empty(),
+        };
+        if changed {
+            self.save();
+        }
+    }
+
+    fn save(&self) {
+        if let Some(parent) = self.config_path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Ok(json) = serde_json::to_string_pretty(&self.decisions) {
+            let _ = fs::write(&self.config_path, json);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::DisplayBounds;
+
+    fn display(id: &str, x: i32, width: u32, height: u32, is_primary: bool) -> DisplayInfo {
+        let bounds = DisplayBounds { x, y: 0, width, height };
+        DisplayInfo {
+            id: id.to_string(),
+            name: Some(id.to_string()),
+            // A 30px panel along the top of every display.
+            work_area: DisplayBounds { x, y: 30, width, height: height - 30 },
+            bounds,
+            scale_factor: 1.0,
+            refresh_rate: None,
+            is_primary,
+        }
+    }
+
+    fn manager(state: WindowState) -> WindowStateManager {
+        WindowStateManager {
+            states: HashMap::from([("main".to_string(), state)]),
+            config_path: PathBuf::new(),
+        }
+    }
+
+    fn saved(x: i32, y: i32, display_id: Option<&str>) -> WindowState {
+        WindowState {
+            x,
+            y,
+            width: 800,
+            height: 600,
+            maximized: false,
+            fullscreen: false,
+            display_id: display_id.map(str::to_string),
+            scale_factor: display_id.map(|_| 1.0),
+        }
+    }
+
+    #[test]
+    fn legacy_state_stays_where_it_was() {
+        let displays = [display("DP-1", 0, 1920, 1080, true), display("DP-2", 1920, 1920, 1080, false)];
+        let state = manager(saved(2100, 200, None)).get_window_state("main", &displays).unwrap();
+        assert_eq!((state.x, state.y), (2100, 200));
+        assert_eq!(state.display_id.as_deref(), Some("DP-2"));
+    }
+
+    #[test]
+    fn legacy_state_is_clamped_into_the_work_area() {
+        let displays = [display("DP-1", 0, 1920, 1080, true)];
+        let state = manager(saved(1500, 0, None)).get_window_state("main", &displays).unwrap();
+        assert_eq!((state.x, state.y), (1920 - 800, 30));
+    }
+
+    #[test]
+    fn renamed_display_keeps_the_position() {
+        // Saved on "HDMI-1", which now shows up as "DP-2" at the same place.
+        let displays = [display("DP-1", 0, 1920, 1080, true), display("DP-2", 1920, 1920, 1080, false)];
+        let state = manager(saved(2000, 100, Some("HDMI-1"))).get_window_state("main", &displays).unwrap();
+        assert_eq!((state.x, state.y), (2000, 100));
+        assert_eq!(state.display_id.as_deref(), Some("DP-2"));
+    }
+
+    #[test]
+    fn moved_display_pulls_the_window_back_on_screen() {
+        // DP-2 used to be at x=1920 and is now 1280 wide, so the window hangs off its edge.
+        let displays = [display("DP-1", 0, 1920, 1080, true), display("DP-2", 1920, 1280, 1024, false)];
+        let state = manager(saved(2800, 500, Some("DP-2"))).get_window_state("main", &displays).unwrap();
+        assert_eq!((state.x, state.y), (1920 + 1280 - 800, 1024 - 600));
+        assert_eq!(state.display_id.as_deref(), Some("DP-2"));
+    }
+
+    #[test]
+    fn off_screen_state_is_centered_on_the_primary_display() {
+        let displays = [display("DP-1", 0, 1920, 1080, false), display("DP-2", 1920, 1920, 1080, true)];
+        let state = manager(saved(5000, 200, Some("DP-3"))).get_window_state("main", &displays).unwrap();
+        assert_eq!((state.x, state.y), (1920 + (1920 - 800) / 2, 30 + (1050 - 600) / 2));
+        assert_eq!(state.display_id.as_deref(), Some("DP-2"));
+    }
+}